@@ -1,5 +1,5 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use hash_hunter::{to_checksum_address, verify_address};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use hash_hunter::{KeyWalker, to_checksum_address, verify_address};
 use rand::rngs::OsRng;
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 
@@ -16,6 +16,18 @@ fn benchmark_address_generation(c: &mut Criterion) {
     });
 }
 
+fn benchmark_key_walk(c: &mut Criterion) {
+    c.bench_function("walk_address_batch", |b| {
+        let mut rng = OsRng;
+        let mut walker = KeyWalker::new(&mut rng);
+
+        b.iter(|| {
+            walker.advance(&mut rng);
+            black_box(walker.public_keys());
+        });
+    });
+}
+
 fn benchmark_checksum(c: &mut Criterion) {
     let test_address = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
     c.bench_function("checksum_address", |b| {
@@ -25,5 +37,21 @@ fn benchmark_checksum(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, benchmark_address_generation, benchmark_checksum);
+fn benchmark_verify(c: &mut Criterion) {
+    let address = "0x1a642f0e3c3af545e7acbd38b07251b3990914f1";
+    let private_key = "0101010101010101010101010101010101010101010101010101010101010101";
+    c.bench_function("verify_address", |b| {
+        b.iter(|| {
+            black_box(verify_address(black_box(address), black_box(private_key)));
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    benchmark_address_generation,
+    benchmark_key_walk,
+    benchmark_checksum,
+    benchmark_verify
+);
 criterion_main!(benches);
@@ -1,11 +1,38 @@
-use hash_hunter::{calculate_years_to_crack, to_checksum_address, verify_address, VanityResult};
+use hash_hunter::account::{SmartAccount, initialize_call, proxy_init_code};
+use hash_hunter::contract::{CREATE3_PROXY_INIT_CODE, clone_init_code};
+use hash_hunter::createx::{CREATEX_ADDRESS, CreateX};
+use hash_hunter::filter::Filter;
+use hash_hunter::hexspeak::{spell, wordlist};
+use hash_hunter::hooks::{HookPermission, hook_bits, hook_flags, hook_permissions};
+use hash_hunter::matcher::{
+    BitMask, ChecksumCase, LeadingZeroBytes, Matcher, MinZeroBytes, MinZeros, NibbleMask, all, any,
+    binomial_tail, hex_checksummed, not, parse_bits,
+};
+use hash_hunter::pattern::Pattern;
+use hash_hunter::safe::{Safe, setup_initializer, uint256_decimal};
+use hash_hunter::score::{
+    calldata_gas, calldata_gas_saving, leading_zero_bytes, leading_zeros, rarity_score, zero_bytes,
+};
+use hash_hunter::validate;
+use hash_hunter::walk::{BATCH_SIZE, ENDOMORPHISM_VARIANTS, KeyWalker};
+use hash_hunter::{
+    Case, Create, Create2, Create3, Difficulty, Preset, Scoring, Search, StopReason, Strategy,
+    Target, TargetSet, VanityResult, address_from_public_key, calculate_years_to_crack,
+    create_address, create2_address, create3_address, entropy_bits, generate_keypair,
+    to_checksum_address, verify_address,
+};
+use rand::rngs::OsRng;
+use regex::Regex;
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use sha3::{Digest, Keccak256};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_checksum_address() {
     let test_address = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
-    let expected = "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+    let expected = "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
     assert_eq!(to_checksum_address(test_address), expected);
 }
 
@@ -26,24 +53,22 @@ fn test_verify_address() {
 #[test]
 fn test_entropy_calculation() {
     let test_cases = vec![
-        ("0x1234567890abcdef", 60.0),
-        ("0x0000000000000000", 60.0),
-        ("0xffffffffffffffff", 60.0),
+        ("0x1234567890abcdef", 64.0),
+        ("0x0000000000000000", 64.0),
+        ("0xffffffffffffffff", 64.0),
     ];
 
     for (address, expected_bits) in test_cases {
-        let address_without_prefix = address.trim_start_matches("0x");
-        let entropy_bits = address_without_prefix.len() * 4;
-        assert_eq!(entropy_bits as f64, expected_bits);
+        assert_eq!(entropy_bits(address) as f64, expected_bits);
     }
 }
 
 #[test]
 fn test_years_to_crack() {
     let test_cases = vec![
-        (128, 1.0e20), // 128-bit should take very long
-        (64, 1.0e8),   // 64-bit should be significantly less
-        (32, 1.0),     // 32-bit should be relatively quick
+        (128, 1.0e19), // 128-bit should take very long
+        (64, 0.5),     // 64-bit should be significantly less
+        (32, 1.0e-10), // 32-bit should be relatively quick
     ];
 
     for (bits, min_years) in test_cases {
@@ -61,14 +86,13 @@ fn test_years_to_crack() {
 #[test]
 fn test_pattern_matching() {
     let test_cases = vec![
-        ("0x1234", "4321", true),
-        ("0xabcd", "dcba", true),
-        ("0x0000", "0000", true),
-        ("0x1234", "5678", false),
+        ("0x1234middle4321", "0x1234", "4321", true),
+        ("0xabcdmiddledcba", "0xabcd", "dcba", true),
+        ("0x0000middle0000", "0x0000", "0000", true),
+        ("0x1234middle4321", "0x1234", "5678", false),
     ];
 
-    for (start, end, should_match) in test_cases {
-        let address = format!("{}middle{}", start, end);
+    for (address, start, end, should_match) in test_cases {
         assert_eq!(
             address.starts_with(start) && address.ends_with(end),
             should_match,
@@ -77,3 +101,1255 @@ fn test_pattern_matching() {
         );
     }
 }
+
+#[test]
+fn test_search_finds_verifiable_key() {
+    let results: Vec<VanityResult> = Search::new()
+        .prefix("0")
+        .suffix("1")
+        .threads(2)
+        .step(1)
+        .run();
+
+    let result = results.first().expect("search should find a match");
+    assert!(result.address.starts_with("0x0"));
+    assert!(result.address.ends_with('1'));
+    assert!(result.attempts > 0);
+    assert!(verify_address(&result.address, result.priv_key().unwrap()));
+}
+
+#[test]
+fn test_matcher_combinators() {
+    let address: [u8; 20] = hex::decode("dead00000000000000000000000000000000beef")
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    assert!(NibbleMask::prefix("DEAD").matches(&address));
+    assert!(NibbleMask::suffix("beef").matches(&address));
+    assert!(NibbleMask::suffix("eef").matches(&address));
+    assert!(!NibbleMask::suffix("bee").matches(&address));
+    assert!(MinZeros(32).matches(&address));
+    assert!(!MinZeros(33).matches(&address));
+
+    let both = all(vec![
+        Box::new(NibbleMask::prefix("dead")),
+        Box::new(NibbleMask::suffix("beef")),
+    ]);
+    assert!(both.matches(&address));
+
+    let either = any(vec![
+        Box::new(NibbleMask::prefix("cafe")),
+        Box::new(NibbleMask::suffix("beef")),
+    ]);
+    assert!(either.matches(&address));
+    assert!(!not(either).matches(&address));
+
+    let custom = |address: &[u8; 20]| address[18..] == [0xbe, 0xef];
+    assert!(custom.matches(&address));
+}
+
+#[test]
+fn test_nibble_mask_merge() {
+    let prefix = NibbleMask::prefix("dead");
+    assert_eq!(prefix.fixed_nibbles(), 4);
+
+    let merged = prefix.merge(&NibbleMask::suffix("beef")).unwrap();
+    assert_eq!(merged.fixed_nibbles(), 8);
+
+    // agreeing overlap merges, conflicting overlap does not
+    assert!(prefix.merge(&NibbleMask::at(2, "ad00")).is_some());
+    assert!(prefix.merge(&NibbleMask::at(3, "e")).is_none());
+}
+
+#[test]
+fn test_key_walker_matches_scalar_multiplication() {
+    let secp = Secp256k1::new();
+    let mut rng = OsRng;
+    let mut walker = KeyWalker::new(&mut rng);
+
+    for _ in 0..2 {
+        walker.advance(&mut rng);
+        let public_keys = walker.public_keys();
+        assert_eq!(public_keys.len(), BATCH_SIZE);
+
+        for i in [0, 1, 511, BATCH_SIZE - 1] {
+            let secret_key = walker.secret_key(i);
+            let expected = PublicKey::from_secret_key(&secp, &secret_key).serialize_uncompressed();
+            assert_eq!(public_keys[i], expected[1..]);
+        }
+    }
+}
+
+#[test]
+fn test_key_walker_endomorphism_variants() {
+    let secp = Secp256k1::new();
+    let mut rng = OsRng;
+    let mut walker = KeyWalker::with_endomorphism(&mut rng);
+    walker.advance(&mut rng);
+
+    let public_keys = walker.public_keys();
+    assert_eq!(public_keys.len(), BATCH_SIZE * ENDOMORPHISM_VARIANTS);
+
+    for variant in 0..ENDOMORPHISM_VARIANTS {
+        for i in [0, 777] {
+            let index = variant * BATCH_SIZE + i;
+            let secret_key = walker.secret_key(index);
+            let expected = PublicKey::from_secret_key(&secp, &secret_key).serialize_uncompressed();
+            assert_eq!(public_keys[index], expected[1..], "variant {}", variant);
+        }
+    }
+}
+
+#[test]
+fn test_search_random_strategy() {
+    let results = Search::new()
+        .strategy(Strategy::Random)
+        .prefix("0")
+        .threads(1)
+        .run();
+
+    let result = &results[0];
+    assert!(verify_address(&result.address, result.priv_key().unwrap()));
+}
+
+#[test]
+fn test_checksum_case_matching() {
+    let address: [u8; 20] = hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        std::str::from_utf8(&hex_checksummed(&address)).unwrap(),
+        "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+
+    assert!(ChecksumCase::prefix("5aAeb").matches(&address));
+    assert!(!ChecksumCase::prefix("5AAeb").matches(&address));
+    assert!(ChecksumCase::suffix("BeAed").matches(&address));
+    assert!(!ChecksumCase::suffix("beaed").matches(&address));
+    assert_eq!(ChecksumCase::prefix("5aAeb").cased_letters(), 4);
+}
+
+#[test]
+fn test_search_exact_case() {
+    let results = Search::new()
+        .case(Case::Exact)
+        .prefix("aB")
+        .threads(2)
+        .run();
+
+    let result = &results[0];
+    assert!(result.address.starts_with("0xaB"));
+    assert_eq!(
+        result.address[2..],
+        to_checksum_address(&result.address[2..])
+    );
+    assert!(verify_address(&result.address, result.priv_key().unwrap()));
+}
+
+#[test]
+fn test_create2_address_eip1014_vectors() {
+    let init_code_hash: [u8; 32] = Keccak256::digest([0x00]).into();
+
+    let address = create2_address(&[0u8; 20], &[0u8; 32], &init_code_hash);
+    assert_eq!(
+        to_checksum_address(&hex::encode(address)),
+        "4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"
+    );
+
+    let mut deployer = [0u8; 20];
+    deployer[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+    let address = create2_address(&deployer, &[0u8; 32], &init_code_hash);
+    assert_eq!(
+        to_checksum_address(&hex::encode(address)),
+        "B928f69Bb1D91Cd65274e3c79d8986362984fDA3"
+    );
+}
+
+#[test]
+fn test_search_create2_salt() {
+    let init_code_hash: [u8; 32] = Keccak256::digest([0x00]).into();
+    let target =
+        Target::Create2(Create2::new([0x11; 20], init_code_hash).salt_prefix(&[0xab, 0xcd]));
+
+    let results = Search::new()
+        .target(target.clone())
+        .prefix("00")
+        .threads(2)
+        .run();
+
+    let result = &results[0];
+    assert!(result.address.starts_with("0x00"));
+    assert!(result.salt().unwrap().starts_with("abcd"));
+    assert!(result.priv_key().is_none());
+    assert!(target.verify(result));
+}
+
+#[test]
+fn test_create_address_nonces() {
+    let deployer: [u8; 20] = hex::decode("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let expected = [
+        "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d",
+        "343c43a37d37dff08ae8c4a11544c718abb4fcf8",
+        "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91",
+        "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c",
+    ];
+    for (nonce, address) in expected.iter().enumerate() {
+        assert_eq!(
+            hex::encode(create_address(&deployer, nonce as u64)),
+            *address
+        );
+    }
+
+    // past 0x7f the nonce becomes a length-prefixed string
+    let mut rlp = vec![0xd8, 0x94];
+    rlp.extend_from_slice(&deployer);
+    rlp.extend_from_slice(&[0x82, 0x01, 0x00]);
+    assert_eq!(
+        create_address(&deployer, 0x100),
+        Keccak256::digest(&rlp)[12..]
+    );
+}
+
+#[test]
+fn test_search_create_nonce() {
+    let target = Target::Create(Create::new(5).match_deployer(true));
+
+    let results = Search::new()
+        .target(target.clone())
+        .prefix("0")
+        .strategy(Strategy::Incremental)
+        .checksum(true)
+        .threads(2)
+        .run();
+
+    let result = &results[0];
+    let deployer = result.deployer.as_deref().unwrap();
+    assert!(result.address.starts_with("0x0"));
+    assert!(deployer.starts_with("0x0"));
+    assert!(verify_address(deployer, result.priv_key().unwrap()));
+    assert!(target.verify(result));
+}
+
+#[test]
+fn test_create3_address() {
+    let factory = [0x22; 20];
+    let salt = [0x33; 32];
+    let create3 = Create3::new(factory);
+    assert_eq!(
+        hex::encode(create3.proxy_init_code_hash),
+        "21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f"
+    );
+
+    // the proxy's first CREATE, whatever the init code
+    let proxy = create2_address(&factory, &salt, &create3.proxy_init_code_hash);
+    assert_eq!(create3.proxy(&salt), proxy);
+    assert_eq!(create3.address(&salt), create_address(&proxy, 1));
+    assert_eq!(
+        create3.address(&salt),
+        create3_address(&factory, &salt, &create3.proxy_init_code_hash)
+    );
+
+    let target = Target::Create3(create3.salt_prefix(&[0x01]));
+    let results = Search::new()
+        .target(target.clone())
+        .suffix("00")
+        .threads(2)
+        .run();
+    let result = &results[0];
+    assert!(result.address.ends_with("00"));
+    assert!(result.salt().unwrap().starts_with("01"));
+    assert!(target.verify(result));
+}
+
+#[test]
+fn test_target_set_lookup() {
+    let targets = TargetSet::parse(
+        "# vanity list\n\
+         dead\n\
+         de\n\
+         *beef\n\
+         dead*cafe\n\
+         ????????????????00000000????????????????\n",
+    )
+    .unwrap()
+    .retire(true);
+    assert_eq!(targets.len(), 5);
+
+    let address = |hex: &str| -> [u8; 20] { hex::decode(hex).unwrap().try_into().unwrap() };
+    let dead_beef = address("deadffffffffffffffffffffffffffffffffbeef");
+    assert!(targets.matches(&dead_beef));
+    assert!(!targets.matches(&address("ffffffffffffffffffffffffffffffffffffffff")));
+    assert!(targets.matches(&address("ffffffffffffffff00000000ffffffffffffffff")));
+
+    // shallowest prefix first, then the next live one, then the suffix
+    assert_eq!(targets.claim(&dead_beef), Some("de"));
+    assert_eq!(targets.claim(&dead_beef), Some("dead"));
+    assert_eq!(targets.claim(&dead_beef), Some("*beef"));
+    assert_eq!(targets.claim(&dead_beef), None);
+    assert_eq!(targets.remaining(), 2);
+
+    assert!(TargetSet::parse("dexd").unwrap_err().starts_with("line 1"));
+    assert!(TargetSet::parse("\n\ndead??").is_err());
+    assert!(TargetSet::parse("*").is_err());
+}
+
+#[test]
+fn test_search_target_set_retires_each_target() {
+    let targets = TargetSet::parse("0\n*1\n????????????????????2???????????????????")
+        .unwrap()
+        .retire(true);
+
+    let results = Search::new().targets(targets).threads(2).run();
+
+    let mut matched: Vec<_> = results
+        .iter()
+        .map(|result| {
+            assert!(verify_address(&result.address, result.priv_key().unwrap()));
+            result.matched.clone().unwrap()
+        })
+        .collect();
+    matched.sort();
+    assert_eq!(
+        matched,
+        ["*1", "0", "????????????????????2???????????????????"]
+    );
+}
+
+#[test]
+fn test_search_max_results_reports_each_hit() {
+    let seen = Arc::new(AtomicUsize::new(0));
+    let search = {
+        let seen = Arc::clone(&seen);
+        Search::new()
+            .prefix("0")
+            .max_results(3)
+            .threads(2)
+            .on_result(move |result| {
+                assert!(verify_address(&result.address, result.priv_key().unwrap()));
+                seen.fetch_add(1, Ordering::Relaxed);
+            })
+    };
+
+    let results = search.run();
+    assert_eq!(results.len(), 3);
+    assert_eq!(seen.load(Ordering::Relaxed), 3);
+    assert_eq!(search.progress().results(), 3);
+    assert_eq!(search.progress().stop_reason(), Some(StopReason::Found));
+}
+
+#[test]
+fn test_search_stop_conditions() {
+    // a 40-nibble prefix that will never turn up
+    let impossible = "dead".repeat(10);
+
+    // the time limits hold however rarely attempts are flushed
+    let search = Search::new()
+        .prefix(&impossible)
+        .timeout(Duration::from_millis(200))
+        .step(u64::MAX)
+        .threads(2);
+    for _ in 0..2 {
+        // each run starts over rather than seeing the last one's stop
+        let start = Instant::now();
+        assert!(search.run().is_empty());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(search.progress().stop_reason(), Some(StopReason::Timeout));
+        assert!(search.progress().attempts() > 0);
+    }
+
+    let stop_file = std::env::temp_dir().join(format!("hash_hunter_stop_{}", std::process::id()));
+    std::fs::write(&stop_file, "").unwrap();
+    let search = Search::new()
+        .prefix(&impossible)
+        .stop_file(&stop_file)
+        .step(u64::MAX)
+        .threads(2);
+    let start = Instant::now();
+    assert!(search.run().is_empty());
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(search.progress().stop_reason(), Some(StopReason::StopFile));
+    std::fs::remove_file(stop_file).unwrap();
+}
+
+#[test]
+fn test_scoring() {
+    let address: [u8; 20] = hex::decode("000a0000ffffffffffffffffffffffffffff0000")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(leading_zeros(&address), 3);
+    assert_eq!(leading_zeros(&[0u8; 20]), 40);
+    assert_eq!(zero_bytes(&address), 5);
+    assert_eq!(Scoring::Zeros.score(&address), 11.0);
+
+    // 3 distinct characters, the most common 'f' 28 times of 40
+    assert_eq!(rarity_score(&address), 3.0 / 16.0 * (1.0 + 28.0 / 40.0));
+    assert!(rarity_score(&[0u8; 20]) < rarity_score(&address));
+}
+
+#[test]
+fn test_zero_byte_matchers() {
+    let address: [u8; 20] = hex::decode("0000a0ffffffffffffffffffffff00ffffff0000")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(leading_zero_bytes(&address), 2);
+    assert!(LeadingZeroBytes(2).matches(&address));
+    assert!(!LeadingZeroBytes(3).matches(&address));
+    assert!(MinZeroBytes(5).matches(&address));
+    assert!(!MinZeroBytes(6).matches(&address));
+    assert_eq!(Scoring::LeadingZeroBytes.score(&address), 2.0);
+
+    // 5 zero bytes at 4 gas, 15 others at 16
+    assert_eq!(calldata_gas(&address), 5 * 4 + 15 * 16);
+    assert!((calldata_gas_saving(&address) - (60.0 - 20.0 * 12.0 / 256.0)).abs() < 1e-9);
+    assert_eq!(calldata_gas(&[0u8; 20]), 80);
+
+    assert_eq!(LeadingZeroBytes(2).probability(), Some(256f64.powi(-2)));
+    assert!((binomial_tail(20, 0.5, 0) - 1.0).abs() < 1e-12);
+    assert!((binomial_tail(2, 0.5, 1) - 0.75).abs() < 1e-12);
+    assert_eq!(binomial_tail(20, 0.5, 21), 0.0);
+    let p = 1.0f64 / 256.0;
+    assert!((MinZeroBytes(20).probability().unwrap() - p.powi(20)).abs() < 1e-60);
+
+    let target = Target::Create(Create::new(0));
+    let results = Search::new()
+        .target(target.clone())
+        .leading_zero_bytes(1)
+        .min_zero_bytes(2)
+        .threads(2)
+        .run();
+    let address: [u8; 20] = hex::decode(&results[0].address[2..])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert!(target.verify(&results[0]));
+    assert!(leading_zero_bytes(&address) >= 1 && zero_bytes(&address) >= 2);
+}
+
+#[test]
+fn test_difficulty() {
+    let difficulty = Difficulty::new(1.0 / 65536.0);
+    assert_eq!(difficulty.bits(), 16.0);
+    assert_eq!(difficulty.success_probability(0), 0.0);
+    let median = difficulty.attempts_for(0.5);
+    assert!((median - 45_425.8).abs() < 1.0);
+    assert!((difficulty.success_probability(median as u64) - 0.5).abs() < 1e-4);
+    assert!((difficulty.seconds_for(0.5, median) - 1.0).abs() < 1e-9);
+
+    // the pinned zeros already satisfy min_zeros
+    let search = Search::new().prefix("000000000000").min_zeros(12);
+    assert_eq!(search.difficulty().unwrap().probability(), 16f64.powi(-12));
+
+    let search = Search::new().prefix("dead").min_zeros(3);
+    let expected = 16f64.powi(-4) * binomial_tail(36, 1.0 / 16.0, 3);
+    assert!((search.difficulty().unwrap().probability() - expected).abs() < 1e-18);
+
+    let search = Search::new()
+        .prefix("De")
+        .case(Case::Exact)
+        .matcher(Preset::FourZeros);
+    assert_eq!(
+        search.difficulty().unwrap().probability(),
+        16f64.powi(-2) * 0.25 * 16f64.powi(-4)
+    );
+
+    let search = Search::new().regex(Regex::new("dead").unwrap());
+    assert!(search.difficulty().is_none());
+}
+
+#[test]
+fn test_validation() {
+    assert_eq!(validate::hex_pattern("0xdead"), Ok("dead"));
+    assert!(
+        validate::hex_pattern("0xg")
+            .unwrap_err()
+            .contains("'g' at character 3")
+    );
+    assert!(validate::hex_pattern(&"0".repeat(41)).is_err());
+
+    // a 21-character prefix and 20-character suffix share character 21
+    let (prefix, suffix) = ("0".repeat(21), "1".to_string() + &"0".repeat(19));
+    assert_eq!(
+        validate::conflict(&prefix, 0, &suffix, 20, Case::Insensitive),
+        Some(20)
+    );
+    assert_eq!(
+        validate::conflict("dEad", 0, "DEad", 0, Case::Insensitive),
+        None
+    );
+    assert_eq!(
+        validate::conflict("dEad", 0, "DEad", 0, Case::Exact),
+        Some(0)
+    );
+
+    let mask = NibbleMask::prefix("dead");
+    assert!(validate::min_zeros(&mask, 36).is_ok());
+    assert!(validate::min_zeros(&mask, 37).is_err());
+    assert!(validate::min_zero_bytes(&NibbleMask::prefix("00a"), 19).is_ok());
+    assert!(validate::min_zero_bytes(&NibbleMask::prefix("00a"), 20).is_err());
+
+    let regex = |pattern: &str, case: Case| validate::regex(&Regex::new(pattern).unwrap(), case);
+    assert!(regex("dead", Case::Insensitive).is_ok());
+    assert!(regex("^[0-9]{40}$", Case::Insensitive).is_ok());
+    assert!(regex("DEAD", Case::Exact).is_ok());
+    assert!(regex("DEAD", Case::Insensitive).is_err());
+    assert!(regex("^dead$", Case::Insensitive).is_err());
+    assert!(regex("[0-9a-f]{41}", Case::Insensitive).is_err());
+    assert!(regex("0x", Case::Insensitive).is_err());
+
+    let hook = hook_bits(hook_flags(&[HookPermission::BeforeSwap]).unwrap());
+    let mut mask = [0u8; 20];
+    let mut value = [0u8; 20];
+    mask[19] = 0xff;
+    value[19] = 0x80;
+    let agrees = BitMask::new(mask, value).unwrap();
+    assert_eq!(validate::bits(&agrees, &hook), None);
+    value[19] = 0x81;
+    let clashes = BitMask::new(mask, value).unwrap();
+    assert_eq!(validate::bits(&clashes, &hook), Some(0));
+    assert_eq!(validate::mask_bits(&NibbleMask::suffix("80"), &hook), None);
+    assert_eq!(
+        validate::mask_bits(&NibbleMask::suffix("81"), &hook),
+        Some(39)
+    );
+
+    let preset = |preset: &str, mask: NibbleMask| {
+        validate::preset(&preset.parse::<Preset>().unwrap(), &mask)
+    };
+    assert!(preset("four-zeros", NibbleMask::prefix("dead")).is_ok());
+    assert!(preset("four-zeros", NibbleMask::prefix("dead1")).is_err());
+    assert!(
+        preset(
+            "palindrome:4",
+            NibbleMask::prefix("12")
+                .merge(&NibbleMask::suffix("21"))
+                .unwrap()
+        )
+        .is_ok()
+    );
+    assert!(
+        preset(
+            "palindrome:4",
+            NibbleMask::prefix("12")
+                .merge(&NibbleMask::suffix("12"))
+                .unwrap()
+        )
+        .is_err()
+    );
+    assert!(preset("leading-palindrome:4", NibbleMask::prefix("123")).is_err());
+    assert!(preset("ascending:16", NibbleMask::prefix("1")).is_ok());
+    assert!(preset("ascending:16", NibbleMask::prefix(&"f".repeat(25))).is_err());
+    assert!(preset("digits:40", NibbleMask::suffix("a")).is_err());
+    assert!(
+        preset(
+            "repeated-byte:20",
+            NibbleMask::prefix("ab")
+                .merge(&NibbleMask::suffix("ab"))
+                .unwrap()
+        )
+        .is_ok()
+    );
+    assert!(
+        preset(
+            "repeated-byte:20",
+            NibbleMask::prefix("ab")
+                .merge(&NibbleMask::suffix("ba"))
+                .unwrap()
+        )
+        .is_err()
+    );
+    let hexspeak_mask = (0..40).step_by(8).fold(NibbleMask::default(), |mask, i| {
+        mask.merge(&NibbleMask::at(i, "0")).unwrap()
+    });
+    assert!(preset("hexspeak", hexspeak_mask).is_err());
+    assert!(preset("hexspeak", NibbleMask::prefix("0")).is_ok());
+}
+
+#[test]
+fn test_filter() {
+    let address: [u8; 20] = hex::decode("dead0000000000000000000000000000000beef1")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let matches = |source: &str| source.parse::<Filter>().unwrap().matches(&address);
+    assert!(matches(
+        "prefix(\"dead\") && (zeros >= 50 || zero_bytes >= 4) && !contains(\"bad\")"
+    ));
+    assert!(matches(
+        "suffix(\"0xBEEF1\") && leading_zeros == 0 && digits > letters"
+    ));
+    assert!(!matches(
+        "prefix(\"dead\") && contains(\"beef\") && !matches(\"f1$\")"
+    ));
+    let checksummed = to_checksum_address(&hex::encode(address));
+    let exact = format!("exact_prefix(\"{}\")", &checksummed[..4]);
+    assert!(matches(&exact));
+    // "dead" is all letters, so flipping their case always breaks it
+    let flipped: String = checksummed[..4]
+        .chars()
+        .map(|c| {
+            if c.is_ascii_uppercase() {
+                c.to_ascii_lowercase()
+            } else {
+                c.to_ascii_uppercase()
+            }
+        })
+        .collect();
+    assert!(!matches(&format!("exact_prefix(\"{}\")", flipped)));
+
+    // derived addresses
+    let contract = hex::encode(create_address(&address, 3));
+    assert!(matches(&format!(
+        "create(3).prefix(\"{}\")",
+        &contract[..8]
+    )));
+    assert!(matches(&format!(
+        "create(3).zeros == {}",
+        contract.matches('0').count()
+    )));
+
+    for (source, error) in [
+        ("zeros >", "column 8"),
+        ("prefix(\"xyz\")", "not a hex digit"),
+        ("foo >= 3", "unknown name foo"),
+        ("zeros = 3", "use =="),
+        ("(zeros > 3", "expected ')'"),
+        ("create2(\"00\", \"00\").zeros > 1", "32 bytes"),
+    ] {
+        let message = source.parse::<Filter>().unwrap_err();
+        assert!(message.contains(error), "{}: {}", source, message);
+    }
+}
+
+#[test]
+fn test_hexspeak_wordlist() {
+    assert_eq!(spell("GoodToast").as_deref(), Some("900d70a57"));
+    assert_eq!(spell("lizards"), None);
+
+    let (words, unspellable) = wordlist("# drinks\ncoffee\nC0FFEE\ntea\nwhiskey\nsoda\n", 4);
+    assert_eq!(unspellable, 1);
+    // coffee and soda, each as prefix or suffix; the repeat spelling and tea
+    // dropped
+    assert_eq!(words.len(), 2);
+
+    let mut address = [0x11u8; 20];
+    address[17..].copy_from_slice(&[0x05, 0x0d, 0xa0]);
+    assert_eq!(words.claim(&address), None);
+    address[17..].copy_from_slice(&[0x11, 0x50, 0xda]);
+    assert_eq!(words.claim(&address), Some("soda"));
+    address[..3].copy_from_slice(&[0xc0, 0xff, 0xee]);
+    assert_eq!(words.claim(&address), Some("coffee"));
+
+    // found once: retiring a word retires both of its spellings
+    let (words, _) = wordlist("coffee\nsoda\n", 4);
+    let words = words.retire(true);
+    let mut address = [0x11u8; 20];
+    address[..3].copy_from_slice(&[0xc0, 0xff, 0xee]);
+    assert_eq!(words.claim(&address), Some("coffee"));
+    assert_eq!(words.remaining(), 1);
+    let mut address = [0x11u8; 20];
+    address[17..].copy_from_slice(&[0xc0, 0xff, 0xee]);
+    assert_eq!(words.claim(&address), None);
+    assert!(!words.matches(&address));
+}
+
+#[test]
+fn test_positional_pattern() {
+    let address: [u8; 20] = hex::decode("1234dead00000000000000000000000000001234")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let matches = |pattern: &str| pattern.parse::<Pattern>().unwrap().matches(&address);
+    assert!(matches("0x????dead*"));
+    assert!(matches("wxyz*wxyz"));
+    assert!(!matches("wxyz*zyxw"));
+    assert!(matches("[0-1][^0]?[4]*[3-4]"));
+    assert!(!matches("[2-9f]*"));
+    assert!(matches(&"?".repeat(40)));
+
+    // a single-character class is pinned, the rest are left for the matcher
+    let pattern: Pattern = "[a]g[0-7]*g".parse().unwrap();
+    assert_eq!(pattern.fixed().fixed_nibbles(), 1);
+    assert_eq!(pattern.probability(), Some(1.0 / 16.0 * 0.5 / 16.0));
+    assert_eq!(
+        pattern.to_string(),
+        format!("ag[01234567]{}g", "?".repeat(36))
+    );
+
+    for (pattern, error) in [
+        ("dead", "4 positions"),
+        ("*dead*", "one *"),
+        ("[0-", "missing its ]"),
+        ("[^0-f]*", "accepts no character"),
+        ("[9-1]*", "backwards"),
+        ("dead#*", "'#'"),
+    ] {
+        let message = pattern.parse::<Pattern>().unwrap_err();
+        assert!(message.contains(error), "{}: {}", pattern, message);
+    }
+
+    let results = Search::new()
+        .pattern(&"x?x*[0-3]".parse().unwrap())
+        .threads(2)
+        .run();
+    let hex = results[0].address[2..].to_lowercase();
+    assert_eq!(hex[0..1], hex[2..3]);
+    assert!(hex.ends_with(['0', '1', '2', '3']));
+}
+
+#[test]
+fn test_bit_mask() {
+    let mask = parse_bits("0x8000_0000_0000_0000_0000_0000_0000_0000_0000_0003").unwrap();
+    assert_eq!((mask[0], mask[19]), (0x80, 0x03));
+    assert_eq!(parse_bits("0b101").unwrap()[19], 0b101);
+    assert!(parse_bits(&format!("0b1{}", "0".repeat(160))).is_err());
+    assert!(parse_bits("0x12g").is_err());
+
+    let bits = BitMask::new(mask, parse_bits("0b01").unwrap()).unwrap();
+    assert_eq!(bits.fixed_bits(), 3);
+    assert_eq!(bits.probability(), Some(0.125));
+    let mut address = [0x7fu8; 20];
+    address[19] = 0xfd;
+    assert!(bits.matches(&address));
+    address[0] = 0xff;
+    assert!(!bits.matches(&address));
+    assert!(BitMask::new(mask, parse_bits("0b100").unwrap()).is_err());
+
+    let target = Target::Create(Create::new(0));
+    let results = Search::new()
+        .target(target.clone())
+        .matcher(bits)
+        .threads(2)
+        .run();
+    let address = hex::decode(&results[0].address[2..]).unwrap();
+    assert!(address[0] < 0x80 && address[19] & 3 == 1);
+    assert!(target.verify(&results[0]));
+}
+
+#[test]
+fn test_uniswap_v4_hook_flags() {
+    use HookPermission::*;
+    assert_eq!(BeforeInitialize.flag(), 1 << 13);
+    assert_eq!(AfterRemoveLiquidityReturnsDelta.flag(), 1);
+    assert_eq!(hook_flags(&[BeforeSwap, AfterSwap]), Ok(0x00c0));
+    assert_eq!(
+        hook_flags(&[AfterSwapReturnsDelta]).unwrap_err(),
+        "after-swap-returns-delta needs after-swap as well"
+    );
+
+    // exactly the flags: before-swap and after-swap set, the other 12 clear
+    let bits = hook_bits(0x00c0);
+    assert_eq!(bits.fixed_bits(), 14);
+    let mut address = [0xffu8; 20];
+    address[18..].copy_from_slice(&[0xc0, 0xc0]);
+    assert!(bits.matches(&address));
+    assert_eq!(hook_permissions(&address), vec![BeforeSwap, AfterSwap]);
+    address[19] = 0xc1;
+    assert!(!bits.matches(&address));
+
+    let create2 = Create2::new([0x11; 20], [0x22; 32]);
+    let results = Search::new()
+        .target(Target::Create2(create2.clone()))
+        .matcher(hook_bits(hook_flags(&[BeforeInitialize]).unwrap()))
+        .threads(2)
+        .run();
+    let address: [u8; 20] = hex::decode(&results[0].address[2..])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(hook_permissions(&address), vec![BeforeInitialize]);
+    assert!(Target::Create2(create2).verify(&results[0]));
+}
+
+#[test]
+fn test_clone_init_code() {
+    let code = clone_init_code(&[0x22; 20]);
+    assert_eq!(
+        hex::encode(code),
+        "3d602d80600a3d3981f3363d3d373d3d3d363d73\
+         2222222222222222222222222222222222222222\
+         5af43d82803e903d91602b57fd5bf3"
+    );
+    let create2 = Create2::clone_of([0x11; 20], &[0x22; 20]);
+    assert_eq!(
+        create2.init_code_hash,
+        <[u8; 32]>::from(Keccak256::digest(code))
+    );
+
+    let results = Search::new()
+        .target(Target::Create2(create2.clone()))
+        .prefix("c1")
+        .threads(2)
+        .run();
+    assert!(results[0].address[2..].to_lowercase().starts_with("c1"));
+    assert!(Target::Create2(create2).verify(&results[0]));
+}
+
+#[test]
+fn test_createx_guarded_salts() {
+    let sender = [0x11; 20];
+    let hash = [0x22; 32];
+    let word = |bytes: &[u8]| {
+        let mut word = vec![0u8; 32 - bytes.len()];
+        word.extend(bytes);
+        word
+    };
+
+    let both = CreateX::create2(hash).sender(sender).chain_id(1);
+    let mut salt = [0x33; 32];
+    salt[..20].copy_from_slice(&sender);
+    salt[20] = 1;
+    assert_eq!(both.salt_prefix(), salt[..21]);
+    let preimage = [word(&sender), word(&[1]), salt.to_vec()].concat();
+    assert_eq!(
+        both.guarded_salt(&salt),
+        <[u8; 32]>::from(Keccak256::digest(preimage))
+    );
+    assert_eq!(
+        both.address(&salt),
+        create2_address(&CREATEX_ADDRESS, &both.guarded_salt(&salt), &hash)
+    );
+
+    let sender_only = CreateX::create2(hash).sender(sender);
+    assert_eq!(sender_only.salt_prefix()[20], 0);
+    let preimage = [word(&sender), salt.to_vec()].concat();
+    assert_eq!(
+        sender_only.guarded_salt(&salt),
+        <[u8; 32]>::from(Keccak256::digest(preimage))
+    );
+
+    let chain_only = CreateX::create3().chain_id(10);
+    let mut prefix = vec![0u8; 20];
+    prefix.push(1);
+    assert_eq!(chain_only.salt_prefix(), prefix);
+    let preimage = [word(&[10]), salt.to_vec()].concat();
+    let guarded: [u8; 32] = Keccak256::digest(preimage).into();
+    assert_eq!(chain_only.guarded_salt(&salt), guarded);
+    assert_eq!(
+        chain_only.address(&salt),
+        create3_address(
+            &CREATEX_ADDRESS,
+            &guarded,
+            &Keccak256::digest(CREATE3_PROXY_INIT_CODE).into()
+        )
+    );
+
+    let unprotected = CreateX::create2(hash);
+    assert_eq!(unprotected.salt_prefix(), [0u8; 21]);
+    let guarded: [u8; 32] = Keccak256::digest(salt).into();
+    assert_eq!(unprotected.guarded_salt(&salt), guarded);
+    assert_eq!(
+        unprotected.address(&salt),
+        create2_address(&CREATEX_ADDRESS, &guarded, &hash)
+    );
+    let results = Search::new()
+        .target(Target::CreateX(unprotected.clone()))
+        .prefix("ab")
+        .threads(2)
+        .run();
+    let mined: [u8; 32] = hex::decode(results[0].salt().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(mined[..21], [0u8; 21]);
+    let address = create2_address(&CREATEX_ADDRESS, &Keccak256::digest(mined).into(), &hash);
+    assert_eq!(
+        results[0].address.to_lowercase(),
+        format!("0x{}", hex::encode(address))
+    );
+
+    let results = Search::new()
+        .target(Target::CreateX(both.clone()))
+        .prefix("ab")
+        .threads(2)
+        .run();
+    assert!(results[0].salt().unwrap().starts_with(&"11".repeat(20)));
+    assert!(Target::CreateX(both).verify(&results[0]));
+}
+
+#[test]
+fn test_safe_salt_nonce() {
+    let owners = [[0x11; 20], [0x22; 20]];
+    let initializer = setup_initializer(&owners, 2, [0x33; 20]).unwrap();
+    assert_eq!(hex::encode(&initializer[..4]), "b63e800d");
+    // 8 head words, the owners array, the empty data
+    assert_eq!(initializer.len(), 4 + 32 * (8 + 3 + 1));
+    assert_eq!(initializer[4 + 32 * 10 - 20..4 + 32 * 10], [0x11; 20]);
+    assert!(setup_initializer(&owners, 3, [0; 20]).is_err());
+    assert!(setup_initializer(&[[0x11; 20], [0x11; 20]], 1, [0; 20]).is_err());
+
+    let code = [0x60, 0x80, 0x60, 0x40, 0x52];
+    let safe = Safe::new([0xaa; 20], &code, [0xbb; 20], &initializer);
+    let mut nonce = [0u8; 32];
+    nonce[31] = 7;
+    let mut salt = Keccak256::digest(&initializer).to_vec();
+    salt.extend(nonce);
+    let mut init_code = code.to_vec();
+    init_code.extend([0u8; 12]);
+    init_code.extend([0xbb; 20]);
+    assert_eq!(
+        safe.address(&nonce),
+        create2_address(
+            &[0xaa; 20],
+            &Keccak256::digest(salt).into(),
+            &Keccak256::digest(init_code).into()
+        )
+    );
+
+    assert_eq!(uint256_decimal(&nonce), "7");
+    assert_eq!(uint256_decimal(&[0; 32]), "0");
+    assert_eq!(
+        uint256_decimal(&[0xff; 32]),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+
+    let results = Search::new()
+        .target(Target::Safe(safe.clone()))
+        .prefix("ab")
+        .threads(2)
+        .run();
+    assert!(results[0].address[2..].to_lowercase().starts_with("ab"));
+    assert!(Target::Safe(safe).verify(&results[0]));
+}
+
+#[test]
+fn test_smart_account_salt() {
+    let call = initialize_call(&[0x11; 20]);
+    assert_eq!(
+        hex::encode(&call),
+        format!("c4d66de8{}{}", "00".repeat(12), "11".repeat(20))
+    );
+
+    // creation code, implementation, offset 0x40, length 36, the call
+    // padded to 64 bytes
+    let code = proxy_init_code(&[0x60, 0x80], &[0x22; 20], &call);
+    assert_eq!(code.len(), 2 + 32 * 3 + 64);
+    assert_eq!(code[2 + 12..2 + 32], [0x22; 20]);
+    assert_eq!(code[2 + 63], 0x40);
+    assert_eq!(code[2 + 95], 36);
+    assert_eq!(code[2 + 96..2 + 132], call[..]);
+    assert!(code[2 + 132..].iter().all(|&b| b == 0));
+
+    let account = SmartAccount::new([0x33; 20], &[0x60, 0x80], [0x22; 20], &call);
+    let salt = [0x44; 32];
+    assert_eq!(
+        account.address(&salt),
+        create2_address(&[0x33; 20], &salt, &Keccak256::digest(&code).into())
+    );
+
+    let results = Search::new()
+        .target(Target::SmartAccount(account.clone()))
+        .prefix("ab")
+        .threads(2)
+        .run();
+    // salts stay below 2^64
+    assert!(results[0].salt().unwrap().starts_with(&"00".repeat(24)));
+    assert!(Target::SmartAccount(account).verify(&results[0]));
+}
+
+#[test]
+fn test_search_leaderboard_keeps_best() {
+    let search = Search::new()
+        .top(3, Scoring::Zeros)
+        .max_tries(20_000)
+        .step(1_000)
+        .threads(2);
+    assert!(search.run().is_empty());
+
+    let entries = search.leaderboard().unwrap().entries();
+    assert_eq!(entries.len(), 3);
+    assert!(
+        entries
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score)
+    );
+    for entry in &entries {
+        assert!(verify_address(
+            &entry.result.address,
+            entry.result.priv_key().unwrap()
+        ));
+        let address: [u8; 20] = hex::decode(&entry.result.address[2..])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(Scoring::Zeros.score(&address), entry.score);
+    }
+    // 20k tries all but guarantee beating the 2.5-zero average
+    assert!(entries[2].score >= 6.0);
+}
+
+#[test]
+fn test_preset_matching() {
+    let address = |hex: &str| -> [u8; 20] { hex::decode(hex).unwrap().try_into().unwrap() };
+
+    let cases = [
+        (
+            "four-zeros",
+            "abcd0000ffffffffffffffffffffffffffffffff",
+            true,
+        ),
+        (
+            "four-zeros",
+            "0000abcdffffffffffffffffffffffffffffffff",
+            false,
+        ),
+        (
+            "triple-digits",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa123",
+            true,
+        ),
+        (
+            "triple-digits",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa12a3",
+            false,
+        ),
+        (
+            "ascending:6",
+            "ffff9abcdeffffffffffffffffffffffffffffff",
+            true,
+        ),
+        (
+            "ascending:6",
+            "ffff89abcfffffffffffffffffffffffffffffff",
+            false,
+        ),
+        (
+            "descending",
+            "ffffffffffffffffffffffffffffffff6543ffff",
+            true,
+        ),
+        (
+            "palindrome:4",
+            "1234ffffffffffffffffffffffffffffffff4321",
+            true,
+        ),
+        (
+            "palindrome:4",
+            "1234ffffffffffffffffffffffffffffffff1234",
+            false,
+        ),
+        (
+            "leading-palindrome",
+            "123456654321ffffffffffffffffffffffffffff",
+            true,
+        ),
+        (
+            "leading-palindrome:5",
+            "12a21fffffffffffffffffffffffffffffffffff",
+            true,
+        ),
+        (
+            "leading-palindrome",
+            "1234ffffffffffffffffffffffffffffffff4321",
+            false,
+        ),
+        (
+            "repeated-byte:3",
+            "120ababab03456789abcdef0123456789abcdef0",
+            false,
+        ),
+        (
+            "repeated-byte:3",
+            "12abababcd3456789abcdef0123456789abcdef0",
+            true,
+        ),
+        ("binary", "ff10101010ffffffffffffffffffffffffffffff", true),
+        ("hexspeak", "ffffffffffffffffffffffffffffffffbaddcafe", true),
+        (
+            "hexspeak",
+            "ffffffffffffffffffffffffffffffffbaddcaff",
+            false,
+        ),
+    ];
+    for (preset, hex, expected) in cases {
+        let preset: Preset = preset.parse().unwrap();
+        assert_eq!(
+            preset.matches(&address(hex)),
+            expected,
+            "{} {}",
+            preset,
+            hex
+        );
+    }
+
+    assert!("ascending:17".parse::<Preset>().is_err());
+    assert!("hexspeak:3".parse::<Preset>().is_err());
+    assert!("sparkly".parse::<Preset>().is_err());
+}
+
+#[test]
+fn test_preset_probabilities() {
+    assert_eq!(Preset::Palindrome(6).probability(), 16f64.powi(-6));
+    assert_eq!(Preset::LeadingPalindrome(12).probability(), 16f64.powi(-6));
+    assert_eq!(Preset::LeadingPalindrome(5).probability(), 16f64.powi(-2));
+    assert_eq!(Preset::Ascending(1).probability(), 1.0);
+    let digit = 1.0 - (6.0f64 / 16.0).powi(40);
+    assert!((Preset::Digits(1).probability() - digit).abs() < 1e-12);
+
+    // the DP against a sample of real candidates
+    let secp = Secp256k1::new();
+    let samples = 4_000;
+    for preset in [Preset::Digits(3), Preset::Ascending(3), Preset::Binary(3)] {
+        let hits = (0..samples)
+            .filter(|_| {
+                let (_, public_key) = generate_keypair(&secp, &mut OsRng);
+                preset.matches(&address_from_public_key(&public_key))
+            })
+            .count();
+        let expected = preset.probability() * samples as f64;
+        let sigma = expected.sqrt();
+        assert!(
+            (hits as f64 - expected).abs() < 5.0 * sigma,
+            "{}: {} hits, expected {:.0}",
+            preset,
+            hits,
+            expected
+        );
+    }
+}
+
+// runs the CLI in a scratch directory, since hits are written to gen/
+fn run_cli(name: &str, args: &[&str]) -> std::process::Output {
+    let dir = std::env::temp_dir().join(format!("hash_hunter_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hash_hunter"))
+        .args(args)
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    output
+}
+
+#[test]
+fn test_cli_checksum_flags() {
+    // a bare -c, as every compose file and Dockerfile passes it
+    let output = run_cli(
+        "checksum",
+        &["-p", "a", "-z", "0", "-c", "-s", "100", "-i", "100", "-y"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Checksum: ✅"));
+
+    let output = run_cli(
+        "no_checksum",
+        &["-p", "a", "-z", "0", "--no-checksum", "-y"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Checksum: ❌"));
+    let address = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Address: "))
+        .unwrap();
+    assert!(!address.contains(|c: char| c.is_ascii_uppercase()));
+}
+
+#[test]
+fn test_cli_timeout_overflow() {
+    let output = run_cli("timeout", &["--timeout", "99999999999999999d", "-y"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("duration too long"));
+
+    // fits in u64 seconds, but not past now as an Instant
+    let output = run_cli(
+        "timeout_max",
+        &[
+            "-p",
+            "a",
+            "-z",
+            "0",
+            "--timeout",
+            "18446744073709551615s",
+            "-y",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_cli_legacy_defaults() {
+    // any criterion turns the 000000000000 prefix and 12 zeros off, -p too
+    let output = run_cli(
+        "legacy_off",
+        &["-p", "a", "-s", "100", "-i", "100", "-y", "createx"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Prefix: a\n"));
+    assert!(stdout.contains("Minimum Zeros: 0\n"));
+
+    // with none at all the Python generator's defaults apply
+    let output = run_cli("legacy_on", &["-m", "1000", "-s", "100", "-y"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Prefix: 000000000000\n"));
+    assert!(stdout.contains("Minimum Zeros: 12\n"));
+}
+
+#[test]
+fn test_cli_gas_report() {
+    // no zero bytes: a little dearer than a random address
+    let output = run_cli(
+        "gas_dear",
+        &["-p", "a", "-z", "0", "--filter", "zero_bytes == 0", "-y"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("(costs 0.94 more per use vs a random address)"));
+
+    let output = run_cli("gas_cheap", &["--leading-zero-bytes", "1", "-z", "0", "-y"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let gas = stdout
+        .lines()
+        .find(|line| line.starts_with("Calldata gas: "))
+        .unwrap();
+    assert!(gas.ends_with(" saved per use vs a random address)"));
+    assert!(!gas.contains('-'));
+}
+
+#[test]
+fn test_measure_rate_is_a_dry_run() {
+    // every candidate matches, so CREATE hashes the deployer of every key
+    let search = Search::new()
+        .target(Target::Create(Create::new(0).match_deployer(true)))
+        .top(3, Scoring::Zeros)
+        .step(u64::MAX)
+        .threads(2);
+    // the measurement keeps to its duration however large the step
+    let start = Instant::now();
+    let rate = search.measure_rate(Duration::from_millis(300));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(rate > 0.0);
+    // hits counted, but nothing ranked or recorded
+    assert!(search.leaderboard().unwrap().entries().is_empty());
+    assert_eq!(search.progress().attempts(), 0);
+    assert_eq!(search.progress().stop_reason(), None);
+}
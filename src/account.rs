@@ -0,0 +1,80 @@
+use crate::contract::create2_address;
+use sha3::{Digest, Keccak256};
+
+/// Signature of `SimpleAccount.initialize`, the call the factory's proxy
+/// makes on deployment.
+pub const INITIALIZE_SIGNATURE: &str = "initialize(address)";
+
+/// Calldata for `SimpleAccount.initialize(owner)`.
+pub fn initialize_call(owner: &[u8; 20]) -> Vec<u8> {
+    let mut calldata = Keccak256::digest(INITIALIZE_SIGNATURE)[..4].to_vec();
+    calldata.extend([0u8; 12]);
+    calldata.extend(owner);
+    calldata
+}
+
+/// Salt mining for an ERC-4337 account from a factory like
+/// `SimpleAccountFactory`, whose `getAddress(owner, salt)` CREATE2s an
+/// ERC1967 proxy for the account implementation.
+///
+/// The proxy's init code is its creation code followed by
+/// `abi.encode(implementation, initCall)`, so the address depends on the
+/// owner through the init call as well as on the salt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartAccount {
+    pub factory: [u8; 20],
+    pub implementation: [u8; 20],
+    pub init_code_hash: [u8; 32],
+}
+
+impl SmartAccount {
+    /// `proxy_creation_code` is `type(ERC1967Proxy).creationCode` as the
+    /// factory was compiled with it; `init_call` is the call the proxy makes
+    /// on the implementation, usually [`initialize_call`].
+    pub fn new(
+        factory: [u8; 20],
+        proxy_creation_code: &[u8],
+        implementation: [u8; 20],
+        init_call: &[u8],
+    ) -> Self {
+        Self {
+            factory,
+            implementation,
+            init_code_hash: Keccak256::digest(proxy_init_code(
+                proxy_creation_code,
+                &implementation,
+                init_call,
+            ))
+            .into(),
+        }
+    }
+
+    /// The counterfactual address of the account created with `salt`.
+    pub fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        create2_address(&self.factory, salt, &self.init_code_hash)
+    }
+}
+
+/// `proxy_creation_code ++ abi.encode(implementation, init_call)`, the init
+/// code of an ERC1967 proxy.
+pub fn proxy_init_code(
+    proxy_creation_code: &[u8],
+    implementation: &[u8; 20],
+    init_call: &[u8],
+) -> Vec<u8> {
+    let word = |value: u64| {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    };
+    let mut code = proxy_creation_code.to_vec();
+    code.extend([0u8; 12]);
+    code.extend(implementation);
+    // the bytes argument: offset, length, then the data padded to a word
+    code.extend(word(64));
+    code.extend(word(init_call.len() as u64));
+    code.extend(init_call);
+    let padding = init_call.len().next_multiple_of(32) - init_call.len();
+    code.extend(std::iter::repeat_n(0u8, padding));
+    code
+}
@@ -0,0 +1,28 @@
+use sha3::{Digest, Keccak256};
+
+/// Converts a hex eth address (no `0x`) to its EIP-55 checksummed casing.
+pub fn to_checksum_address(address: &str) -> String {
+    let address = address.to_lowercase();
+    let hash = Keccak256::digest(address.as_bytes());
+    let mut checksum_address = String::with_capacity(40);
+
+    for (i, c) in address.chars().enumerate() {
+        if c.is_ascii_digit() {
+            checksum_address.push(c);
+        } else {
+            let hash_byte = hash[i / 2];
+            let nibble = if i % 2 == 0 {
+                hash_byte >> 4
+            } else {
+                hash_byte & 0x0F
+            };
+            if nibble >= 8 {
+                checksum_address.push(c.to_ascii_uppercase());
+            } else {
+                checksum_address.push(c);
+            }
+        }
+    }
+    // ggez
+    checksum_address
+}
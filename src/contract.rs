@@ -0,0 +1,245 @@
+use rand::RngCore;
+use sha3::{Digest, Keccak256};
+
+/// Init code of the minimal proxy CREATE3 factories deploy with CREATE2; it
+/// CREATEs whatever init code it is called with.
+pub const CREATE3_PROXY_INIT_CODE: [u8; 16] = [
+    0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60, 0x18, 0xf3,
+];
+
+/// EIP-1167 minimal proxy init code up to the implementation address.
+pub const CLONE_INIT_CODE_PREFIX: [u8; 20] = [
+    0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d,
+    0x3d, 0x36, 0x3d, 0x73,
+];
+
+/// EIP-1167 minimal proxy init code after the implementation address.
+pub const CLONE_INIT_CODE_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+/// Init code of an EIP-1167 clone of `implementation`, as
+/// `Clones.cloneDeterministic` deploys it.
+pub fn clone_init_code(implementation: &[u8; 20]) -> [u8; 55] {
+    let mut code = [0u8; 55];
+    code[..20].copy_from_slice(&CLONE_INIT_CODE_PREFIX);
+    code[20..40].copy_from_slice(implementation);
+    code[40..].copy_from_slice(&CLONE_INIT_CODE_SUFFIX);
+    code
+}
+
+/// Salt bytes a [`Create2`] or [`Create3`] search may fix up front; the last 8 bytes are
+/// always left to the per-thread counter.
+pub const MAX_SALT_PREFIX: usize = 24;
+
+/// Address of a CREATE2 deployment:
+/// `keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]`.
+pub fn create2_address(
+    deployer: &[u8; 20],
+    salt: &[u8; 32],
+    init_code_hash: &[u8; 32],
+) -> [u8; 20] {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(deployer);
+    preimage[21..53].copy_from_slice(salt);
+    preimage[53..].copy_from_slice(init_code_hash);
+    Keccak256::digest(preimage)[12..].try_into().unwrap()
+}
+
+/// Address of a CREATE deployment: `keccak256(rlp([deployer, nonce]))[12..]`.
+pub fn create_address(deployer: &[u8; 20], nonce: u64) -> [u8; 20] {
+    // rlp: a 20-byte string, then the nonce as a minimal big-endian integer
+    // (0 is the empty string, 1..=0x7f is its own byte)
+    let mut rlp = [0u8; 31];
+    rlp[1] = 0x80 + 20;
+    rlp[2..22].copy_from_slice(deployer);
+    let mut len = 22;
+    match nonce {
+        0 => {
+            rlp[len] = 0x80;
+            len += 1;
+        }
+        1..=0x7f => {
+            rlp[len] = nonce as u8;
+            len += 1;
+        }
+        _ => {
+            let bytes = nonce.to_be_bytes();
+            let skip = nonce.leading_zeros() as usize / 8;
+            rlp[len] = 0x80 + (8 - skip) as u8;
+            rlp[len + 1..len + 1 + 8 - skip].copy_from_slice(&bytes[skip..]);
+            len += 1 + 8 - skip;
+        }
+    }
+    rlp[0] = 0xc0 + (len - 1) as u8;
+    Keccak256::digest(&rlp[..len])[12..].try_into().unwrap()
+}
+
+/// Key mining for the contract a fresh EOA deploys with CREATE at a given
+/// nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Create {
+    pub nonce: u64,
+    /// whether the deploying EOA must match the criteria too
+    pub match_deployer: bool,
+}
+
+impl Create {
+    pub fn new(nonce: u64) -> Self {
+        Self {
+            nonce,
+            match_deployer: false,
+        }
+    }
+
+    /// Applies the search criteria to the deployer's own address as well.
+    pub fn match_deployer(mut self, match_deployer: bool) -> Self {
+        self.match_deployer = match_deployer;
+        self
+    }
+
+    /// The address `deployer` creates at this nonce.
+    pub fn address(&self, deployer: &[u8; 20]) -> [u8; 20] {
+        create_address(deployer, self.nonce)
+    }
+}
+
+/// Address of a CREATE3 deployment: the proxy lands at
+/// `create2_address(factory, salt, proxy_init_code_hash)` and deploys the
+/// contract with CREATE at nonce 1.
+pub fn create3_address(
+    factory: &[u8; 20],
+    salt: &[u8; 32],
+    proxy_init_code_hash: &[u8; 32],
+) -> [u8; 20] {
+    create_address(&create2_address(factory, salt, proxy_init_code_hash), 1)
+}
+
+/// Salt mining for a CREATE2 deployment from a fixed deployer (usually a
+/// factory contract) and init code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Create2 {
+    pub deployer: [u8; 20],
+    pub init_code_hash: [u8; 32],
+    /// leading salt bytes to keep fixed, at most [`MAX_SALT_PREFIX`]
+    pub salt_prefix: Vec<u8>,
+}
+
+impl Create2 {
+    pub fn new(deployer: [u8; 20], init_code_hash: [u8; 32]) -> Self {
+        Self {
+            deployer,
+            init_code_hash,
+            salt_prefix: Vec::new(),
+        }
+    }
+
+    /// EIP-1167 clones of `implementation` deployed by `deployer`.
+    pub fn clone_of(deployer: [u8; 20], implementation: &[u8; 20]) -> Self {
+        Self::new(
+            deployer,
+            Keccak256::digest(clone_init_code(implementation)).into(),
+        )
+    }
+
+    /// Fixes the leading bytes of every salt tried.
+    ///
+    /// # Panics
+    ///
+    /// If `prefix` is longer than [`MAX_SALT_PREFIX`].
+    pub fn salt_prefix(mut self, prefix: &[u8]) -> Self {
+        assert!(
+            prefix.len() <= MAX_SALT_PREFIX,
+            "salt prefix longer than 24 bytes"
+        );
+        self.salt_prefix = prefix.to_vec();
+        self
+    }
+
+    /// The address `salt` deploys to.
+    pub fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        create2_address(&self.deployer, salt, &self.init_code_hash)
+    }
+}
+
+/// Salt mining for a CREATE3 deployment, whose address depends only on the
+/// factory and salt, not on the contract's init code.
+///
+/// The salt is the one the factory passes to CREATE2; factories that mix the
+/// caller into it need that done before their salts can be mined here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Create3 {
+    pub factory: [u8; 20],
+    pub proxy_init_code_hash: [u8; 32],
+    /// leading salt bytes to keep fixed, at most [`MAX_SALT_PREFIX`]
+    pub salt_prefix: Vec<u8>,
+}
+
+impl Create3 {
+    /// A factory deploying the standard [`CREATE3_PROXY_INIT_CODE`] proxy.
+    pub fn new(factory: [u8; 20]) -> Self {
+        Self {
+            factory,
+            proxy_init_code_hash: Keccak256::digest(CREATE3_PROXY_INIT_CODE).into(),
+            salt_prefix: Vec::new(),
+        }
+    }
+
+    /// For factories deploying a different proxy.
+    pub fn proxy_init_code_hash(mut self, hash: [u8; 32]) -> Self {
+        self.proxy_init_code_hash = hash;
+        self
+    }
+
+    /// Fixes the leading bytes of every salt tried.
+    ///
+    /// # Panics
+    ///
+    /// If `prefix` is longer than [`MAX_SALT_PREFIX`].
+    pub fn salt_prefix(mut self, prefix: &[u8]) -> Self {
+        assert!(
+            prefix.len() <= MAX_SALT_PREFIX,
+            "salt prefix longer than 24 bytes"
+        );
+        self.salt_prefix = prefix.to_vec();
+        self
+    }
+
+    /// The proxy `salt` deploys.
+    pub fn proxy(&self, salt: &[u8; 32]) -> [u8; 20] {
+        create2_address(&self.factory, salt, &self.proxy_init_code_hash)
+    }
+
+    /// The address the contract deployed with `salt` ends up at.
+    pub fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        create_address(&self.proxy(salt), 1)
+    }
+}
+
+/// Per-thread salt source: the fixed prefix, random filler up to byte 24,
+/// then a big-endian counter in the last 8 bytes. The counter starts at a
+/// random value too, so threads stay apart even when the prefix leaves no
+/// room for filler.
+pub(crate) struct SaltCounter {
+    salt: [u8; 32],
+    counter: u64,
+}
+
+impl SaltCounter {
+    pub(crate) fn new<R: RngCore>(prefix: &[u8], rng: &mut R) -> Self {
+        let mut salt = [0u8; 32];
+        salt[..prefix.len()].copy_from_slice(prefix);
+        rng.fill_bytes(&mut salt[prefix.len()..MAX_SALT_PREFIX]);
+        Self {
+            salt,
+            counter: rng.next_u64(),
+        }
+    }
+
+    pub(crate) fn next_salt(&mut self) -> [u8; 32] {
+        self.salt[MAX_SALT_PREFIX..].copy_from_slice(&self.counter.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        self.salt
+    }
+}
@@ -0,0 +1,126 @@
+use crate::contract::{CREATE3_PROXY_INIT_CODE, create_address, create2_address};
+use sha3::{Digest, Keccak256};
+
+/// Where CreateX is deployed, the same address on every chain it supports.
+pub const CREATEX_ADDRESS: [u8; 20] = [
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d, 0x5f, 0x7b, 0xdc, 0x13, 0x05, 0xd3,
+    0xc2, 0x8b, 0xa5, 0xed,
+];
+
+/// Salt mining for a deployment through CreateX's `deployCreate2` or
+/// `deployCreate3`.
+///
+/// CreateX reads protection flags out of the salt and hashes it into a
+/// guarded salt before deploying, so the salts mined here are laid out the
+/// way it expects:
+///
+/// - the sender in the first 20 bytes makes the salt usable only by that
+///   sender (permissioned deploy protection)
+/// - byte 21 set to `0x01` ties the address to one chain (cross-chain
+///   redeploy protection), with the zero address up front when there is no
+///   sender
+///
+/// Without either, the salt starts with the zero address and a `0x00` flag
+/// byte, which CreateX only accepts as unprotected and hashes on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateX {
+    pub factory: [u8; 20],
+    /// the only account allowed to deploy with the salt
+    pub sender: Option<[u8; 20]>,
+    /// the only chain the salt deploys to this address on
+    pub chain_id: Option<u64>,
+    /// keccak256 of the init code for `deployCreate2`, none for
+    /// `deployCreate3`
+    pub init_code_hash: Option<[u8; 32]>,
+    /// keccak256 of the proxy `deployCreate3` goes through
+    pub proxy_init_code_hash: [u8; 32],
+}
+
+impl CreateX {
+    /// `deployCreate2` of the init code hashing to `init_code_hash`.
+    pub fn create2(init_code_hash: [u8; 32]) -> Self {
+        Self {
+            factory: CREATEX_ADDRESS,
+            sender: None,
+            chain_id: None,
+            init_code_hash: Some(init_code_hash),
+            proxy_init_code_hash: Keccak256::digest(CREATE3_PROXY_INIT_CODE).into(),
+        }
+    }
+
+    /// `deployCreate3`, whose address does not depend on the init code.
+    pub fn create3() -> Self {
+        Self {
+            factory: CREATEX_ADDRESS,
+            sender: None,
+            chain_id: None,
+            init_code_hash: None,
+            proxy_init_code_hash: Keccak256::digest(CREATE3_PROXY_INIT_CODE).into(),
+        }
+    }
+
+    /// Enables permissioned deploy protection for `sender`.
+    pub fn sender(mut self, sender: [u8; 20]) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Enables cross-chain redeploy protection on `chain_id`.
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// For a CreateX deployed somewhere other than [`CREATEX_ADDRESS`].
+    pub fn factory(mut self, factory: [u8; 20]) -> Self {
+        self.factory = factory;
+        self
+    }
+
+    /// The salt bytes the protection flags fix: sender (or zero address)
+    /// and flag byte. CreateX reverts on any flag byte but `0x00` or `0x01`
+    /// there, so it is pinned even when neither protection is on.
+    pub fn salt_prefix(&self) -> Vec<u8> {
+        let mut prefix = self.sender.unwrap_or_default().to_vec();
+        prefix.push(self.chain_id.is_some() as u8);
+        prefix
+    }
+
+    /// The salt CreateX deploys with after `_guard`.
+    pub fn guarded_salt(&self, salt: &[u8; 32]) -> [u8; 32] {
+        // up to two left-padded words ahead of the salt
+        let mut preimage = [0u8; 96];
+        let mut len = 0;
+        if let Some(sender) = &self.sender {
+            preimage[len + 12..len + 32].copy_from_slice(sender);
+            len += 32;
+        }
+        if let Some(chain_id) = self.chain_id {
+            preimage[len + 24..len + 32].copy_from_slice(&chain_id.to_be_bytes());
+            len += 32;
+        }
+        preimage[len..len + 32].copy_from_slice(salt);
+        Keccak256::digest(&preimage[..len + 32]).into()
+    }
+
+    /// The CREATE3 proxy CreateX deploys for `salt`, if this is a
+    /// `deployCreate3`.
+    pub fn proxy(&self, salt: &[u8; 32]) -> Option<[u8; 20]> {
+        if self.init_code_hash.is_some() {
+            return None;
+        }
+        Some(create2_address(
+            &self.factory,
+            &self.guarded_salt(salt),
+            &self.proxy_init_code_hash,
+        ))
+    }
+
+    /// The address the contract deployed with `salt` ends up at.
+    pub fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        match &self.init_code_hash {
+            Some(hash) => create2_address(&self.factory, &self.guarded_salt(salt), hash),
+            None => create_address(&self.proxy(salt).unwrap(), 1),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+/// How hard a hunt is, from the chance that a single attempt matches.
+///
+/// Attempts are independent, so the number needed is geometric: the chance
+/// of a hit within `n` attempts is `1 - (1 - p)^n`.
+///
+/// ```
+/// use hash_hunter::Difficulty;
+///
+/// let difficulty = Difficulty::new(16f64.powi(-4));
+/// assert_eq!(difficulty.expected_attempts(), 65536.0);
+/// assert!((difficulty.success_probability(45_426) - 0.5).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    probability: f64,
+}
+
+impl Difficulty {
+    /// Percentiles worth reporting for a hunt.
+    pub const CONFIDENCES: [f64; 3] = [0.5, 0.9, 0.99];
+
+    pub fn new(probability: f64) -> Self {
+        Self { probability }
+    }
+
+    /// Chance that one attempt matches.
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Difficulty in bits, `-log2(p)`.
+    pub fn bits(&self) -> f64 {
+        -self.probability.log2()
+    }
+
+    /// Mean attempts to the first match.
+    pub fn expected_attempts(&self) -> f64 {
+        1.0 / self.probability
+    }
+
+    /// Chance of at least one match within `attempts`.
+    pub fn success_probability(&self, attempts: u64) -> f64 {
+        // ln_1p and exp_m1 keep tiny probabilities from rounding to 0
+        -(attempts as f64 * (-self.probability).ln_1p()).exp_m1()
+    }
+
+    /// Attempts after which a match has been found with chance `confidence`.
+    pub fn attempts_for(&self, confidence: f64) -> f64 {
+        (-confidence).ln_1p() / (-self.probability).ln_1p()
+    }
+
+    /// Seconds to reach `confidence` at `rate` attempts per second.
+    pub fn seconds_for(&self, confidence: f64, rate: f64) -> f64 {
+        self.attempts_for(confidence) / rate
+    }
+}
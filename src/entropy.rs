@@ -0,0 +1,11 @@
+/// Bits of entropy carried by a hex address (4 per character, `0x` ignored).
+pub fn entropy_bits(address: &str) -> usize {
+    address.trim_start_matches("0x").len() * 4
+}
+
+/// Years needed to brute force `entropy_bits` at a trillion guesses per second.
+pub fn calculate_years_to_crack(entropy_bits: usize) -> f64 {
+    let guesses_per_second = 1e12; // assume 1 trillion guesses per second
+    let seconds_to_crack = 2f64.powi(entropy_bits as i32) / guesses_per_second;
+    seconds_to_crack / (365.25 * 24.0 * 60.0 * 60.0)
+}
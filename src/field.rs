@@ -0,0 +1,171 @@
+// arithmetic modulo the secp256k1 field prime p = 2^256 - 2^32 - 977, just
+// enough for affine point addition in the key walker
+
+/// p as little-endian limbs
+const P: [u64; 4] = [
+    0xFFFF_FFFE_FFFF_FC2F,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+];
+
+/// 2^256 mod p
+const C: u64 = 0x1_0000_03D1;
+
+/// A field element, always fully reduced into `[0, p)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct FieldElement([u64; 4]);
+
+impl FieldElement {
+    pub(crate) const ZERO: Self = Self([0; 4]);
+
+    /// Parses a 32-byte big-endian value, which must already be below p.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, chunk) in bytes[..32].chunks_exact(8).enumerate() {
+            limbs[3 - i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        debug_assert!(!geq_p(&limbs));
+        Self(limbs)
+    }
+
+    /// Writes the element as 32 big-endian bytes.
+    pub(crate) fn write_bytes(&self, out: &mut [u8]) {
+        for (i, chunk) in out[..32].chunks_exact_mut(8).enumerate() {
+            chunk.copy_from_slice(&self.0[3 - i].to_be_bytes());
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub(crate) fn sub(&self, rhs: &Self) -> Self {
+        let mut r = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in r.iter_mut().enumerate() {
+            let (v, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (v, b2) = v.overflowing_sub(borrow as u64);
+            *limb = v;
+            borrow = b1 || b2;
+        }
+        if borrow {
+            let mut carry = 0u128;
+            for (i, limb) in r.iter_mut().enumerate() {
+                let v = *limb as u128 + P[i] as u128 + carry;
+                *limb = v as u64;
+                carry = v >> 64;
+            }
+        }
+        Self(r)
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    pub(crate) fn mul(&self, rhs: &Self) -> Self {
+        // schoolbook 256x256 -> 512
+        let mut t = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let v = t[i + j] as u128 + self.0[i] as u128 * rhs.0[j] as u128 + carry;
+                t[i + j] = v as u64;
+                carry = v >> 64;
+            }
+            t[i + 4] = carry as u64;
+        }
+
+        // fold the high half down: hi * 2^256 = hi * C (mod p)
+        let mut r = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let v = t[i] as u128 + t[i + 4] as u128 * C as u128 + carry;
+            r[i] = v as u64;
+            carry = v >> 64;
+        }
+
+        // the leftover carry is at most ~2^34, fold it the same way
+        while carry != 0 {
+            let mut c = carry * C as u128;
+            for limb in r.iter_mut() {
+                let v = *limb as u128 + c;
+                *limb = v as u64;
+                c = v >> 64;
+            }
+            carry = c;
+        }
+
+        if geq_p(&r) {
+            sub_p(&mut r);
+        }
+        Self(r)
+    }
+
+    pub(crate) fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Multiplicative inverse via Fermat, a^(p-2). Zero maps to zero.
+    pub(crate) fn invert(&self) -> Self {
+        let mut exponent = P;
+        exponent[0] -= 2;
+
+        let mut result = Self([1, 0, 0, 0]);
+        for limb in exponent.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.square();
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(self);
+                }
+            }
+        }
+        result
+    }
+}
+
+fn geq_p(r: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if r[i] != P[i] {
+            return r[i] > P[i];
+        }
+    }
+    true
+}
+
+fn sub_p(r: &mut [u64; 4]) {
+    let mut borrow = false;
+    for (i, limb) in r.iter_mut().enumerate() {
+        let (v, b1) = limb.overflowing_sub(P[i]);
+        let (v, b2) = v.overflowing_sub(borrow as u64);
+        *limb = v;
+        borrow = b1 || b2;
+    }
+}
+
+/// Inverts every element of `values` in place with a single field inversion
+/// (Montgomery's trick). `scratch` is reused between calls to avoid
+/// allocating. Returns false, leaving `values` untouched, if any is zero.
+pub(crate) fn batch_invert(values: &mut [FieldElement], scratch: &mut Vec<FieldElement>) -> bool {
+    scratch.clear();
+    let mut acc = FieldElement([1, 0, 0, 0]);
+    for v in values.iter() {
+        acc = acc.mul(v);
+        scratch.push(acc);
+    }
+    if acc.is_zero() {
+        return false;
+    }
+
+    let mut inv = acc.invert();
+    for i in (1..values.len()).rev() {
+        let inv_i = inv.mul(&scratch[i - 1]);
+        inv = inv.mul(&values[i]);
+        values[i] = inv_i;
+    }
+    if let Some(first) = values.first_mut() {
+        *first = inv;
+    }
+    true
+}
@@ -0,0 +1,546 @@
+use crate::contract::{Create3, create_address, create2_address, create3_address};
+use crate::matcher::{Matcher, hex_checksummed, hex_lower, zero_nibbles};
+use crate::score::{calldata_gas, leading_zero_bytes, leading_zeros, rarity_score, zero_bytes};
+use crate::validate;
+use regex::bytes::Regex;
+use std::str::FromStr;
+
+/// A filter expression, parsed once and evaluated per candidate.
+///
+/// Conditions combine with `&&`, `||`, `!` and parentheses. Numbers compare
+/// with `==`, `!=`, `<`, `<=`, `>` and `>=`:
+///
+/// ```text
+/// prefix("dead") && (zeros >= 10 || zero_bytes >= 4) && !contains("bad")
+/// exact_suffix("BeeF") || create(0).leading_zero_bytes >= 2
+/// ```
+///
+/// Properties and tests apply to the candidate address, or to an address
+/// derived from it when written after one:
+///
+/// - `address`: the candidate itself
+/// - `create(N)`: the contract it deploys with CREATE at nonce N
+/// - `create2("salt", "init code hash")`: its CREATE2 deployment
+/// - `create3("salt")`: its CREATE3 deployment through the standard proxy
+///
+/// Numbers: `zeros`, `leading_zeros`, `zero_bytes`, `leading_zero_bytes`,
+/// `letters`, `digits`, `rarity` and `gas` (calldata gas). Tests:
+/// `prefix`, `suffix`, `contains` and `matches` (a regex) on the lowercase
+/// hex, and `exact_prefix`, `exact_suffix`, `exact_contains` and
+/// `exact_matches` on the EIP-55 checksummed hex.
+///
+/// ```
+/// use hash_hunter::filter::Filter;
+/// use hash_hunter::Matcher;
+///
+/// let filter: Filter = "prefix(\"dead\") && zero_bytes >= 1".parse().unwrap();
+/// let mut address = [0u8; 20];
+/// address[..2].copy_from_slice(&[0xde, 0xad]);
+/// assert!(filter.matches(&address));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Filter {
+    source: String,
+    expr: Expr,
+}
+
+impl Filter {
+    /// The expression as written.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            next: 0,
+            end: source.len(),
+        };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.next) {
+            None => Ok(Self {
+                source: source.to_string(),
+                expr,
+            }),
+            Some((at, token)) => Err(format!("column {}: unexpected {}", at + 1, token)),
+        }
+    }
+}
+
+impl Matcher for Filter {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.expr.eval(address)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Number, Comparison, Number),
+    Text(Subject, TextTest, bool, Vec<u8>),
+    Regex(Subject, bool, Regex),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TextTest {
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Number {
+    Literal(f64),
+    Property(Subject, Property),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Property {
+    Zeros,
+    LeadingZeros,
+    ZeroBytes,
+    LeadingZeroBytes,
+    Letters,
+    Digits,
+    Rarity,
+    Gas,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Subject {
+    Address,
+    Create(u64),
+    Create2([u8; 32], [u8; 32]),
+    Create3([u8; 32], [u8; 32]),
+}
+
+impl Subject {
+    fn resolve(&self, address: &[u8; 20]) -> [u8; 20] {
+        match self {
+            Subject::Address => *address,
+            Subject::Create(nonce) => create_address(address, *nonce),
+            Subject::Create2(salt, init_code_hash) => {
+                create2_address(address, salt, init_code_hash)
+            }
+            Subject::Create3(salt, proxy_hash) => create3_address(address, salt, proxy_hash),
+        }
+    }
+}
+
+impl Property {
+    fn eval(&self, address: &[u8; 20]) -> f64 {
+        let letters = || {
+            address
+                .iter()
+                .map(|b| (b >> 4 > 9) as usize + (b & 0x0F > 9) as usize)
+                .sum::<usize>()
+        };
+        match self {
+            Property::Zeros => zero_nibbles(address) as f64,
+            Property::LeadingZeros => leading_zeros(address) as f64,
+            Property::ZeroBytes => zero_bytes(address) as f64,
+            Property::LeadingZeroBytes => leading_zero_bytes(address) as f64,
+            Property::Letters => letters() as f64,
+            Property::Digits => (40 - letters()) as f64,
+            Property::Rarity => rarity_score(address),
+            Property::Gas => calldata_gas(address) as f64,
+        }
+    }
+}
+
+impl Number {
+    fn eval(&self, address: &[u8; 20]) -> f64 {
+        match self {
+            Number::Literal(value) => *value,
+            Number::Property(subject, property) => property.eval(&subject.resolve(address)),
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, address: &[u8; 20]) -> bool {
+        match self {
+            Expr::Literal(value) => *value,
+            Expr::Not(inner) => !inner.eval(address),
+            Expr::And(a, b) => a.eval(address) && b.eval(address),
+            Expr::Or(a, b) => a.eval(address) || b.eval(address),
+            Expr::Compare(a, comparison, b) => {
+                let (a, b) = (a.eval(address), b.eval(address));
+                match comparison {
+                    Comparison::Eq => a == b,
+                    Comparison::Ne => a != b,
+                    Comparison::Lt => a < b,
+                    Comparison::Le => a <= b,
+                    Comparison::Gt => a > b,
+                    Comparison::Ge => a >= b,
+                }
+            }
+            Expr::Text(subject, test, exact, pattern) => {
+                let hex = hex_of(&subject.resolve(address), *exact);
+                match test {
+                    TextTest::Prefix => hex.starts_with(pattern),
+                    TextTest::Suffix => hex.ends_with(pattern),
+                    TextTest::Contains => hex.windows(pattern.len()).any(|w| w == &pattern[..]),
+                }
+            }
+            Expr::Regex(subject, exact, regex) => {
+                regex.is_match(&hex_of(&subject.resolve(address), *exact))
+            }
+        }
+    }
+}
+
+fn hex_of(address: &[u8; 20], exact: bool) -> [u8; 40] {
+    if exact {
+        hex_checksummed(address)
+    } else {
+        hex_lower(address)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Punct(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "number {}", n),
+            Token::Str(s) => write!(f, "string \"{}\"", s),
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Punct(p) => write!(f, "'{}'", p),
+        }
+    }
+}
+
+// longest first, so "<=" wins over "<"
+const PUNCTUATION: [&str; 14] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "(", ")", ",", ".", "=",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(at, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut end = at;
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_ascii_digit() && c != '.' {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            let number = source[at..end]
+                .parse()
+                .map_err(|_| format!("column {}: bad number {}", at + 1, &source[at..end]))?;
+            tokens.push((at, Token::Number(number)));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = at;
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_ascii_alphanumeric() && c != '_' {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            tokens.push((at, Token::Ident(source[at..end].to_string())));
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c)) => text.push(c),
+                        None => break,
+                    },
+                    Some((_, c)) => text.push(c),
+                    None => return Err(format!("column {}: unterminated string", at + 1)),
+                }
+            }
+            tokens.push((at, Token::Str(text)));
+        } else {
+            let punct = PUNCTUATION
+                .iter()
+                .find(|p| source[at..].starts_with(**p))
+                .ok_or_else(|| format!("column {}: unexpected '{}'", at + 1, c))?;
+            if *punct == "=" {
+                return Err(format!("column {}: use == to compare", at + 1));
+            }
+            for _ in 0..punct.len() {
+                chars.next();
+            }
+            tokens.push((at, Token::Punct(punct)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // column reported for a missing token
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn at(&self) -> usize {
+        self.tokens.get(self.next).map_or(self.end, |(at, _)| *at) + 1
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), String> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", punct)))
+        }
+    }
+
+    fn unexpected(&self, wanted: &str) -> String {
+        match self.peek() {
+            Some(token) => format!("column {}: expected {}, found {}", self.at(), wanted, token),
+            None => format!("column {}: expected {}, found the end", self.at(), wanted),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        match self.peek() {
+            Some(Token::Ident(name)) if name == "true" || name == "false" => {
+                let value = name == "true";
+                self.next += 1;
+                Ok(Expr::Literal(value))
+            }
+            _ => self.condition(),
+        }
+    }
+
+    // a test on an address, or a comparison of two numbers
+    fn condition(&mut self) -> Result<Expr, String> {
+        let left = match self.term()? {
+            Term::Test(expr) => return Ok(expr),
+            Term::Number(number) => number,
+        };
+        let comparison = [
+            ("==", Comparison::Eq),
+            ("!=", Comparison::Ne),
+            ("<=", Comparison::Le),
+            (">=", Comparison::Ge),
+            ("<", Comparison::Lt),
+            (">", Comparison::Gt),
+        ]
+        .into_iter()
+        .find(|(punct, _)| self.eat(punct))
+        .map(|(_, comparison)| comparison)
+        .ok_or_else(|| self.unexpected("a comparison"))?;
+        match self.term()? {
+            Term::Number(right) => Ok(Expr::Compare(left, comparison, right)),
+            Term::Test(_) => Err(format!(
+                "column {}: expected a number to compare with",
+                self.at()
+            )),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, String> {
+        let column = self.at();
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.next += 1;
+                Ok(Term::Number(Number::Literal(value)))
+            }
+            Some(Token::Ident(name)) => {
+                self.next += 1;
+                let subject = match name.as_str() {
+                    "address" => Some(Subject::Address),
+                    "create" => {
+                        self.expect("(")?;
+                        let nonce = self.number()?;
+                        if nonce.fract() != 0.0 || !(0.0..=u64::MAX as f64).contains(&nonce) {
+                            return Err(format!("column {}: bad nonce {}", column, nonce));
+                        }
+                        self.expect(")")?;
+                        Some(Subject::Create(nonce as u64))
+                    }
+                    "create2" => {
+                        self.expect("(")?;
+                        let salt = self.bytes32("salt")?;
+                        self.expect(",")?;
+                        let init_code_hash = self.bytes32("init code hash")?;
+                        self.expect(")")?;
+                        Some(Subject::Create2(salt, init_code_hash))
+                    }
+                    "create3" => {
+                        self.expect("(")?;
+                        let salt = self.bytes32("salt")?;
+                        self.expect(")")?;
+                        let proxy_hash = Create3::new([0; 20]).proxy_init_code_hash;
+                        Some(Subject::Create3(salt, proxy_hash))
+                    }
+                    _ => None,
+                };
+                match subject {
+                    Some(subject) => {
+                        self.expect(".")?;
+                        let column = self.at();
+                        match self.peek().cloned() {
+                            Some(Token::Ident(member)) => {
+                                self.next += 1;
+                                self.member(subject, &member, column)
+                            }
+                            _ => Err(self.unexpected("a property or test")),
+                        }
+                    }
+                    None => self.member(Subject::Address, &name, column),
+                }
+            }
+            _ => Err(self.unexpected("a condition")),
+        }
+    }
+
+    fn member(&mut self, subject: Subject, name: &str, column: usize) -> Result<Term, String> {
+        let property = match name {
+            "zeros" => Some(Property::Zeros),
+            "leading_zeros" => Some(Property::LeadingZeros),
+            "zero_bytes" => Some(Property::ZeroBytes),
+            "leading_zero_bytes" => Some(Property::LeadingZeroBytes),
+            "letters" => Some(Property::Letters),
+            "digits" => Some(Property::Digits),
+            "rarity" => Some(Property::Rarity),
+            "gas" => Some(Property::Gas),
+            _ => None,
+        };
+        if let Some(property) = property {
+            return Ok(Term::Number(Number::Property(subject, property)));
+        }
+
+        let (exact, test) = match name.strip_prefix("exact_") {
+            Some(test) => (true, test),
+            None => (false, name),
+        };
+        let test = match test {
+            "prefix" => Some(TextTest::Prefix),
+            "suffix" => Some(TextTest::Suffix),
+            "contains" => Some(TextTest::Contains),
+            "matches" => None,
+            _ => return Err(format!("column {}: unknown name {}", column, name)),
+        };
+        self.expect("(")?;
+        let argument_column = self.at();
+        let argument = self.string()?;
+        self.expect(")")?;
+
+        let expr = match test {
+            Some(test) => {
+                let hex = validate::hex_pattern(&argument)
+                    .map_err(|e| format!("column {}: {}", argument_column, e))?;
+                let hex = if exact {
+                    hex.to_string()
+                } else {
+                    hex.to_ascii_lowercase()
+                };
+                Expr::Text(subject, test, exact, hex.into_bytes())
+            }
+            None => {
+                let regex = Regex::new(&argument)
+                    .map_err(|e| format!("column {}: {}", argument_column, e))?;
+                Expr::Regex(subject, exact, regex)
+            }
+        };
+        Ok(Term::Test(expr))
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some(&Token::Number(value)) => {
+                self.next += 1;
+                Ok(value)
+            }
+            _ => Err(self.unexpected("a number")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        match self.peek().cloned() {
+            Some(Token::Str(text)) => {
+                self.next += 1;
+                Ok(text)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    fn bytes32(&mut self, what: &str) -> Result<[u8; 32], String> {
+        let column = self.at();
+        let text = self.string()?;
+        hex::decode(text.strip_prefix("0x").unwrap_or(&text))
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| format!("column {}: {} must be 32 bytes of hex", column, what))
+    }
+}
+
+enum Term {
+    Test(Expr),
+    Number(Number),
+}
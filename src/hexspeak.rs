@@ -0,0 +1,68 @@
+use crate::matcher::NibbleMask;
+use crate::targets::TargetSet;
+use std::collections::HashSet;
+
+// letters outside a-f and the digits they pass for
+const LEET: [(char, char); 7] = [
+    ('o', '0'),
+    ('i', '1'),
+    ('l', '1'),
+    ('z', '2'),
+    ('s', '5'),
+    ('t', '7'),
+    ('g', '9'),
+];
+
+/// Hex spelling of `word`: hex letters and digits stay, and `o`, `i`, `l`,
+/// `z`, `s`, `t` and `g` become the digits they look like. `None` if the
+/// word has any other character.
+///
+/// ```
+/// use hash_hunter::hexspeak::spell;
+///
+/// assert_eq!(spell("Coffee").as_deref(), Some("c0ffee"));
+/// assert_eq!(spell("l33t").as_deref(), Some("1337"));
+/// assert_eq!(spell("hunter"), None);
+/// ```
+pub fn spell(word: &str) -> Option<String> {
+    word.chars()
+        .map(|c| {
+            let c = c.to_ascii_lowercase();
+            if c.is_ascii_hexdigit() {
+                Some(c)
+            } else {
+                LEET.iter()
+                    .find(|(letter, _)| *letter == c)
+                    .map(|&(_, digit)| digit)
+            }
+        })
+        .collect()
+}
+
+/// Every spellable word in `text`, one per line, as a target matching it as
+/// either prefix or suffix, labelled with the word so a hit reports the word
+/// it spells and retires both spellings. Blank lines and `#` comments are
+/// skipped, as are words shorter than `min_length` or longer than an address
+/// and repeat spellings.
+///
+/// Returns the targets and the number of words that could not be spelled.
+pub fn wordlist(text: &str, min_length: usize) -> (TargetSet, usize) {
+    let mut set = TargetSet::new();
+    let mut seen = HashSet::new();
+    let mut unspellable = 0;
+    for word in text.lines().map(str::trim) {
+        if word.is_empty() || word.starts_with('#') {
+            continue;
+        }
+        let Some(hex) = spell(word) else {
+            unspellable += 1;
+            continue;
+        };
+        if hex.len() < min_length || hex.len() > 40 || !seen.insert(hex.clone()) {
+            continue;
+        }
+        set.push(word, NibbleMask::prefix(&hex));
+        set.push(word, NibbleMask::suffix(&hex));
+    }
+    (set, unspellable)
+}
@@ -0,0 +1,107 @@
+use crate::matcher::BitMask;
+use clap::ValueEnum;
+use std::fmt;
+
+/// A Uniswap v4 hook callback, enabled by a flag in the low 14 bits of the
+/// hook's address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HookPermission {
+    BeforeInitialize,
+    AfterInitialize,
+    BeforeAddLiquidity,
+    AfterAddLiquidity,
+    BeforeRemoveLiquidity,
+    AfterRemoveLiquidity,
+    BeforeSwap,
+    AfterSwap,
+    BeforeDonate,
+    AfterDonate,
+    BeforeSwapReturnsDelta,
+    AfterSwapReturnsDelta,
+    AfterAddLiquidityReturnsDelta,
+    AfterRemoveLiquidityReturnsDelta,
+}
+
+/// Address bits v4 reads hook permissions from.
+pub const ALL_HOOK_FLAGS: u16 = (1 << 14) - 1;
+
+impl HookPermission {
+    /// Every permission, highest flag first as in `Hooks.sol`.
+    pub const ALL: [HookPermission; 14] = [
+        HookPermission::BeforeInitialize,
+        HookPermission::AfterInitialize,
+        HookPermission::BeforeAddLiquidity,
+        HookPermission::AfterAddLiquidity,
+        HookPermission::BeforeRemoveLiquidity,
+        HookPermission::AfterRemoveLiquidity,
+        HookPermission::BeforeSwap,
+        HookPermission::AfterSwap,
+        HookPermission::BeforeDonate,
+        HookPermission::AfterDonate,
+        HookPermission::BeforeSwapReturnsDelta,
+        HookPermission::AfterSwapReturnsDelta,
+        HookPermission::AfterAddLiquidityReturnsDelta,
+        HookPermission::AfterRemoveLiquidityReturnsDelta,
+    ];
+
+    /// The permission's bit, `1 << 13` for `BeforeInitialize` down to `1`.
+    pub fn flag(self) -> u16 {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap();
+        1 << (13 - index)
+    }
+
+    // the callback a returns-delta permission modifies
+    fn requires(self) -> Option<HookPermission> {
+        match self {
+            HookPermission::BeforeSwapReturnsDelta => Some(HookPermission::BeforeSwap),
+            HookPermission::AfterSwapReturnsDelta => Some(HookPermission::AfterSwap),
+            HookPermission::AfterAddLiquidityReturnsDelta => {
+                Some(HookPermission::AfterAddLiquidity)
+            }
+            HookPermission::AfterRemoveLiquidityReturnsDelta => {
+                Some(HookPermission::AfterRemoveLiquidity)
+            }
+            _ => None,
+        }
+    }
+}
+
+// the CLI spelling, e.g. before-swap
+impl fmt::Display for HookPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().unwrap();
+        write!(f, "{}", value.get_name())
+    }
+}
+
+/// Flag bits for `permissions`. Fails on a returns-delta permission without
+/// its callback, which `Hooks.isValidHookAddress` rejects.
+pub fn hook_flags(permissions: &[HookPermission]) -> Result<u16, String> {
+    for permission in permissions {
+        if let Some(required) = permission.requires()
+            && !permissions.contains(&required)
+        {
+            return Err(format!("{} needs {} as well", permission, required));
+        }
+    }
+    Ok(permissions.iter().fold(0, |flags, p| flags | p.flag()))
+}
+
+/// Matches hook addresses whose low 14 bits are exactly `flags`: the
+/// permissions asked for set, every other one clear.
+pub fn hook_bits(flags: u16) -> BitMask {
+    let mut mask = [0u8; 20];
+    let mut value = [0u8; 20];
+    mask[18..].copy_from_slice(&ALL_HOOK_FLAGS.to_be_bytes());
+    value[18..].copy_from_slice(&(flags & ALL_HOOK_FLAGS).to_be_bytes());
+    BitMask::new(mask, value).unwrap()
+}
+
+/// Permissions a hook at `address` has.
+pub fn hook_permissions(address: &[u8; 20]) -> Vec<HookPermission> {
+    let flags = u16::from_be_bytes([address[18], address[19]]);
+    HookPermission::ALL
+        .into_iter()
+        .filter(|p| flags & p.flag() != 0)
+        .collect()
+}
@@ -0,0 +1,48 @@
+use rand::RngCore;
+use secp256k1::{PublicKey, Secp256k1, SecretKey, Signing};
+use sha3::{Digest, Keccak256};
+
+/// Generates a fresh secp256k1 keypair from `rng`.
+pub fn generate_keypair<C: Signing, R: RngCore>(
+    secp: &Secp256k1<C>,
+    rng: &mut R,
+) -> (SecretKey, PublicKey) {
+    let secret_key = SecretKey::new(rng);
+    let public_key = PublicKey::from_secret_key(secp, &secret_key);
+    (secret_key, public_key)
+}
+
+/// Derives the 20-byte eth address of a public key: the last 20 bytes of
+/// keccak-256 over the uncompressed key without its `0x04` tag.
+pub fn address_from_public_key(public_key: &PublicKey) -> [u8; 20] {
+    let serialized_pub = public_key.serialize_uncompressed();
+    let hash = Keccak256::digest(&serialized_pub[1..]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Derives the 20-byte eth address owned by `secret_key`.
+pub fn address_from_secret_key<C: Signing>(
+    secp: &Secp256k1<C>,
+    secret_key: &SecretKey,
+) -> [u8; 20] {
+    address_from_public_key(&PublicKey::from_secret_key(secp, secret_key))
+}
+
+/// Checks that `private_key` (hex) controls `address` (hex, `0x` optional,
+/// any casing). Malformed input never verifies.
+pub fn verify_address(address: &str, private_key: &str) -> bool {
+    let Ok(key_bytes) = hex::decode(private_key.trim_start_matches("0x")) else {
+        return false;
+    };
+    let Ok(secret_key) = SecretKey::from_slice(&key_bytes) else {
+        return false;
+    };
+
+    let secp = Secp256k1::signing_only();
+    let generated_address = hex::encode(address_from_secret_key(&secp, &secret_key));
+
+    address.trim_start_matches("0x").to_lowercase() == generated_address
+}
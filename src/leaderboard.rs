@@ -0,0 +1,90 @@
+use crate::score::Scoring;
+use crate::search::VanityResult;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A result and its score.
+#[derive(Debug, Clone)]
+pub struct Ranked {
+    pub score: f64,
+    pub result: VanityResult,
+}
+
+/// The best `size` addresses seen so far under a [`Scoring`], best first.
+///
+/// Workers only take the lock for candidates that beat the current cutoff,
+/// which once the board fills up is nearly none of them.
+#[derive(Debug)]
+pub struct Leaderboard {
+    size: usize,
+    scoring: Scoring,
+    entries: Mutex<Vec<Ranked>>,
+    // rank key the next entry must beat, as f64 bits
+    cutoff: AtomicU64,
+}
+
+impl Leaderboard {
+    pub fn new(size: usize, scoring: Scoring) -> Self {
+        Self {
+            size: size.max(1),
+            scoring,
+            entries: Mutex::new(Vec::new()),
+            cutoff: AtomicU64::new(f64::NEG_INFINITY.to_bits()),
+        }
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Snapshot of the board, best first.
+    pub fn entries(&self) -> Vec<Ranked> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Whether `score` would make the board.
+    pub fn qualifies(&self, score: f64) -> bool {
+        self.rank_key(score) > f64::from_bits(self.cutoff.load(Ordering::Relaxed))
+    }
+
+    /// Adds a result if it makes the board; `result` is only built if so.
+    pub fn offer(&self, score: f64, result: impl FnOnce() -> VanityResult) {
+        if !self.qualifies(score) {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        // recheck under the lock, the cutoff may have moved
+        if !self.qualifies(score) {
+            return;
+        }
+
+        let key = self.rank_key(score);
+        // ties keep the earlier find ahead
+        let position = entries
+            .iter()
+            .position(|entry| self.rank_key(entry.score) < key)
+            .unwrap_or(entries.len());
+        entries.insert(
+            position,
+            Ranked {
+                score,
+                result: result(),
+            },
+        );
+        entries.truncate(self.size);
+
+        if entries.len() == self.size {
+            let last = self.rank_key(entries[self.size - 1].score);
+            self.cutoff.store(last.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    // higher is better
+    fn rank_key(&self, score: f64) -> f64 {
+        if self.scoring.lower_is_better() {
+            -score
+        } else {
+            score
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Ethereum vanity address generation.
+//!
+//! The `hash_hunter` binary is a thin CLI over this crate; embed [`Search`]
+//! directly to hunt for addresses from your own tools.
+
+pub mod account;
+pub mod checksum;
+pub mod contract;
+pub mod createx;
+pub mod difficulty;
+pub mod entropy;
+mod field;
+pub mod filter;
+pub mod hexspeak;
+pub mod hooks;
+pub mod keys;
+pub mod leaderboard;
+pub mod matcher;
+pub mod pattern;
+pub mod preset;
+pub mod safe;
+pub mod score;
+pub mod search;
+pub mod targets;
+pub mod validate;
+pub mod walk;
+
+pub use account::SmartAccount;
+pub use checksum::to_checksum_address;
+pub use contract::{Create, Create2, Create3, create_address, create2_address, create3_address};
+pub use createx::CreateX;
+pub use difficulty::Difficulty;
+pub use entropy::{calculate_years_to_crack, entropy_bits};
+pub use keys::{
+    address_from_public_key, address_from_secret_key, generate_keypair, verify_address,
+};
+pub use leaderboard::{Leaderboard, Ranked};
+pub use matcher::{Case, Matcher};
+pub use preset::Preset;
+pub use safe::Safe;
+pub use score::Scoring;
+pub use search::{Progress, Search, Secret, StopReason, Strategy, Target, VanityResult};
+pub use targets::TargetSet;
+pub use walk::KeyWalker;
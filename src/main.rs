@@ -1,63 +1,364 @@
 use chrono::Utc;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::*;
-use dashmap::DashMap;
+use hash_hunter::account::{SmartAccount, initialize_call};
+use hash_hunter::contract::{MAX_SALT_PREFIX, clone_init_code};
+use hash_hunter::difficulty::Difficulty;
+use hash_hunter::filter::Filter;
+use hash_hunter::hexspeak;
+use hash_hunter::hooks::{HookPermission, hook_bits, hook_flags, hook_permissions};
+use hash_hunter::matcher::{BitMask, ChecksumCase, NibbleMask, parse_bits};
+use hash_hunter::pattern::Pattern;
+use hash_hunter::safe::{Safe, setup_initializer, uint256_decimal};
+use hash_hunter::score::{calldata_gas, calldata_gas_saving};
+use hash_hunter::validate;
+use hash_hunter::{
+    Case, Create, Create2, Create3, CreateX, Leaderboard, Preset, Scoring, Search, Secret,
+    StopReason, Strategy, Target, TargetSet, VanityResult, to_checksum_address,
+};
 use indicatif::{ProgressBar, ProgressStyle};
-use rand::rngs::OsRng;
 use regex::Regex;
-use secp256k1::{PublicKey, Secp256k1, SecretKey};
-use sha3::{Digest, Keccak256};
 use std::fs::OpenOptions;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 // eth addy gen in rust, zooms
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    // prefix of the eth address
-    #[arg(short = 'p', long, default_value = "000000000000")]
-    start_pattern: String,
+    // prefix of the eth address (000000000000 when no criterion at all is given)
+    #[arg(global = true, short = 'p', long)]
+    start_pattern: Option<String>,
 
     // suffix of the eth address
-    #[arg(short = 'e', long, default_value = "")]
+    #[arg(global = true, short = 'e', long, default_value = "")]
     end_pattern: String,
 
-    // enable EIP-55 checksum
-    #[arg(short = 'c', long, default_value_t = true)]
+    // report addresses with EIP-55 checksum casing (the default; kept so -c still parses)
+    #[arg(global = true, short = 'c', long, overrides_with = "no_checksum")]
     checksum: bool,
 
+    // report addresses in lowercase hex instead
+    #[arg(global = true, long, overrides_with = "checksum")]
+    no_checksum: bool,
+
+    // insensitive: match on lowercase hex; exact: pattern letters must match the EIP-55 casing
+    #[arg(global = true, long, value_enum, default_value_t = Case::Insensitive)]
+    case: Case,
+
     // # of attempts between progress logs
-    #[arg(short = 's', long, default_value_t = 50_000)]
+    #[arg(global = true, short = 's', long, default_value_t = 50_000)]
     step: u64,
 
     // max # of attempts
-    #[arg(short = 'm', long, default_value_t = 10_000_000_000)]
+    #[arg(global = true, short = 'm', long, default_value_t = 10_000_000_000)]
     max_tries: u64,
 
     // logging interval in ms
-    #[arg(short = 'i', long, default_value_t = 15_000)]
+    #[arg(global = true, short = 'i', long, default_value_t = 15_000)]
     log_interval: u64,
 
-    // minimum number of zeros in the address
-    #[arg(short = 'z', long, default_value_t = 12)]
-    min_zeros: usize,
+    // minimum number of zeros in the address (12 when no criterion at all is given)
+    #[arg(global = true, short = 'z', long)]
+    min_zeros: Option<usize>,
+
+    // require this many leading 0x00 bytes, for cheaper calldata
+    #[arg(global = true, long, value_name = "N")]
+    leading_zero_bytes: Option<usize>,
+
+    // require this many 0x00 bytes anywhere, for cheaper calldata
+    #[arg(global = true, long, value_name = "N")]
+    min_zero_bytes: Option<usize>,
+
+    // stop after this many addresses (default 1, or every --targets line with --retire)
+    #[arg(global = true, short = 'n', long)]
+    max_results: Option<usize>,
+
+    // keep hunting until another limit is hit, saving every address found
+    #[arg(
+        global = true,
+        long,
+        default_value_t = false,
+        conflicts_with = "max_results"
+    )]
+    keep_hunting: bool,
+
+    // give up after this long: 90, 90s, 15m, 8h or 2d
+    #[arg(global = true, long, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    // stop as soon as this file exists, e.g. touch gen/STOP
+    #[arg(global = true, long)]
+    stop_file: Option<PathBuf>,
+
+    // named pattern, repeatable: four-zeros, triple-digits, digits[:N], ascending[:N],
+    // descending[:N], palindrome[:N], leading-palindrome[:N], repeated-byte[:N], binary[:N],
+    // hexspeak
+    #[arg(global = true, long, value_name = "NAME[:N]")]
+    preset: Vec<Preset>,
+
+    // keep the K best-scoring addresses instead of stopping on a match, saved to gen/leaderboard.json
+    #[arg(global = true, long, value_name = "K")]
+    top: Option<usize>,
+
+    // what --top ranks by
+    #[arg(global = true, long, value_enum, default_value_t = Scoring::Zeros)]
+    score: Scoring,
+
+    // file of patterns to hunt at once, one per line: dead, *beef, dead*beef or a 40-char ? mask
+    #[arg(global = true, short = 't', long)]
+    targets: Option<PathBuf>,
+
+    // wordlist, one per line: any word spelled in hex (o=0, i/l=1, z=2, s=5, t=7, g=9) as prefix or suffix
+    #[arg(global = true, long, conflicts_with = "targets")]
+    words: Option<PathBuf>,
+
+    // shortest hex spelling --words hunts for
+    #[arg(global = true, long, default_value_t = 4)]
+    min_word_length: usize,
+
+    // keep hunting after a hit until every target in --targets or --words is found once
+    #[arg(global = true, long, default_value_t = false)]
+    retire: bool,
+
+    // all 40 characters: hex pins, ? any, [0-7] a class, g-z equal variables, one * fills, e.g. ????dead*
+    #[arg(global = true, long, value_name = "PATTERN")]
+    mask: Option<Pattern>,
+
+    // bits of the 160-bit address to check, as 0x hex or 0b binary counted from the lowest bit
+    #[arg(global = true, long, value_name = "MASK", value_parser = parse_bits)]
+    bitmask: Option<[u8; 20]>,
+
+    // what the --bitmask bits must hold (all zero by default)
+    #[arg(global = true, long, value_name = "VALUE", value_parser = parse_bits, requires = "bitmask")]
+    bitvalue: Option<[u8; 20]>,
+
+    // expression every address must satisfy, e.g. 'prefix("dead") && (zeros >= 10 || zero_bytes >= 4)'
+    #[arg(global = true, long, value_name = "EXPR")]
+    filter: Option<Filter>,
 
     // regex pattern to match in the address
-    #[arg(short = 'r', long, default_value = "")]
+    #[arg(global = true, short = 'r', long, default_value = "")]
     regex_pattern: String,
 
+    // how candidate keys are generated
+    #[arg(global = true, long, value_enum, default_value_t = Strategy::Endomorphism)]
+    strategy: Strategy,
+
     // skip confirmation prompt for docker
-    #[arg(short = 'y', long, default_value_t = false)]
+    #[arg(global = true, short = 'y', long, default_value_t = false)]
     skip_confirmation: bool,
+
+    // what to hunt for; private keys for an EOA when omitted
+    #[command(subcommand)]
+    mode: Option<Mode>,
 }
 
-struct VanityResult {
-    address: String,
-    priv_key: String,
-    attempts: u64,
+// the Python generator's defaults, for a run that asks for nothing else
+const LEGACY_START_PATTERN: &str = "000000000000";
+const LEGACY_MIN_ZEROS: usize = 12;
+
+impl Args {
+    // -p, or the legacy prefix when no criterion at all is given
+    fn start_pattern(&self) -> &str {
+        match &self.start_pattern {
+            Some(pattern) => pattern,
+            None if self.has_criteria() => "",
+            None => LEGACY_START_PATTERN,
+        }
+    }
+
+    // -z, or the legacy count when no criterion at all is given
+    fn min_zeros(&self) -> usize {
+        match self.min_zeros {
+            Some(min_zeros) => min_zeros,
+            None if self.has_criteria() => 0,
+            None => LEGACY_MIN_ZEROS,
+        }
+    }
+
+    // whether any argument narrows down the addresses accepted
+    fn has_criteria(&self) -> bool {
+        self.start_pattern.is_some()
+            || self.min_zeros.is_some()
+            || !self.end_pattern.is_empty()
+            || !self.regex_pattern.is_empty()
+            || self.leading_zero_bytes.is_some()
+            || self.min_zero_bytes.is_some()
+            || !self.preset.is_empty()
+            || self.top.is_some()
+            || self.targets.is_some()
+            || self.words.is_some()
+            || self.mask.is_some()
+            || self.bitmask.is_some()
+            || self.filter.is_some()
+            || matches!(self.mode, Some(Mode::Hook { .. }))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Mode {
+    /// Mine a CREATE2 salt so a factory deploys your contract at a vanity address
+    Create2 {
+        // the contract calling CREATE2 (factory)
+        #[arg(long, value_parser = parse_address)]
+        deployer: [u8; 20],
+
+        // keccak256 of the contract's init code
+        #[arg(long, value_parser = parse_hash)]
+        init_code_hash: [u8; 32],
+
+        // leading salt bytes to keep fixed (hex, up to 24 bytes)
+        #[arg(long, value_parser = parse_salt_prefix, default_value = "")]
+        salt_prefix: ::std::vec::Vec<u8>,
+    },
+    /// Mine a CREATE3 salt so a factory deploys at a vanity address, whatever the bytecode
+    Create3 {
+        // the CREATE3 factory
+        #[arg(long, value_parser = parse_address)]
+        factory: [u8; 20],
+
+        // keccak256 of the factory's proxy init code; the common minimal proxy when omitted
+        #[arg(long, value_parser = parse_hash)]
+        proxy_init_code_hash: Option<[u8; 32]>,
+
+        // leading salt bytes to keep fixed (hex, up to 24 bytes)
+        #[arg(long, value_parser = parse_salt_prefix, default_value = "")]
+        salt_prefix: ::std::vec::Vec<u8>,
+    },
+    /// Mine a CREATE2 salt for a Uniswap v4 hook whose address enables exactly the given callbacks
+    Hook {
+        // the contract calling CREATE2 (factory)
+        #[arg(long, value_parser = parse_address)]
+        deployer: [u8; 20],
+
+        // keccak256 of the hook's init code, constructor arguments included
+        #[arg(long, value_parser = parse_hash)]
+        init_code_hash: [u8; 32],
+
+        // callbacks the hook implements, comma separated, e.g. before-swap,after-swap
+        #[arg(long, value_enum, value_delimiter = ',', required = true)]
+        permissions: Vec<HookPermission>,
+
+        // leading salt bytes to keep fixed (hex, up to 24 bytes)
+        #[arg(long, value_parser = parse_salt_prefix, default_value = "")]
+        salt_prefix: ::std::vec::Vec<u8>,
+    },
+    /// Mine a CREATE2 salt so a factory's Clones.cloneDeterministic lands an EIP-1167 clone at a vanity address
+    Clone {
+        // the contract calling cloneDeterministic (factory)
+        #[arg(long, value_parser = parse_address)]
+        deployer: [u8; 20],
+
+        // the implementation the clone delegates to
+        #[arg(long, value_parser = parse_address)]
+        implementation: [u8; 20],
+
+        // leading salt bytes to keep fixed (hex, up to 24 bytes)
+        #[arg(long, value_parser = parse_salt_prefix, default_value = "")]
+        salt_prefix: ::std::vec::Vec<u8>,
+    },
+    /// Recompute the address an EIP-1167 clone deploys to from its salt, without searching
+    VerifyClone {
+        // the contract calling cloneDeterministic (factory)
+        #[arg(long, value_parser = parse_address)]
+        deployer: [u8; 20],
+
+        // the implementation the clone delegates to
+        #[arg(long, value_parser = parse_address)]
+        implementation: [u8; 20],
+
+        // the salt passed to cloneDeterministic
+        #[arg(long, value_parser = parse_hash)]
+        salt: [u8; 32],
+
+        // the address reported for the salt, to check against
+        #[arg(long, value_parser = parse_address)]
+        address: Option<[u8; 20]>,
+    },
+    /// Mine a CreateX salt, with optional sender and cross-chain protection, for deployCreate2 or deployCreate3
+    #[command(name = "createx")]
+    CreateX {
+        // keccak256 of the init code for deployCreate2; deployCreate3 when omitted
+        #[arg(long, value_parser = parse_hash)]
+        init_code_hash: Option<[u8; 32]>,
+
+        // the account calling CreateX, embedded in the salt so only it can deploy with it
+        #[arg(long, value_parser = parse_address)]
+        sender: Option<[u8; 20]>,
+
+        // the chain to tie the salt to (cross-chain redeploy protection)
+        #[arg(long)]
+        chain_id: Option<u64>,
+
+        // CreateX's address, if not the canonical 0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed
+        #[arg(long, value_parser = parse_address)]
+        factory: Option<[u8; 20]>,
+    },
+    /// Mine a saltNonce for createProxyWithNonce so a Safe lands at a vanity address
+    Safe {
+        // the Safe proxy factory
+        #[arg(long, value_parser = parse_address)]
+        factory: [u8; 20],
+
+        // the Safe singleton (mastercopy) the proxy delegates to
+        #[arg(long, value_parser = parse_address)]
+        singleton: [u8; 20],
+
+        // what the factory's proxyCreationCode() returns (hex)
+        #[arg(long, value_parser = parse_hex)]
+        proxy_creation_code: ::std::vec::Vec<u8>,
+
+        // Safe owners, comma separated
+        #[arg(long, value_parser = parse_address, value_delimiter = ',', required_unless_present = "initializer")]
+        owners: Vec<[u8; 20]>,
+
+        // confirmations required to execute a transaction
+        #[arg(long, default_value_t = 1)]
+        threshold: u64,
+
+        // fallback handler set up with the Safe; none when omitted
+        #[arg(long, value_parser = parse_address)]
+        fallback_handler: Option<[u8; 20]>,
+
+        // full initializer calldata (hex), instead of setup built from the owners
+        #[arg(long, value_parser = parse_hex, conflicts_with_all = ["owners", "fallback_handler"])]
+        initializer: Option<::std::vec::Vec<u8>>,
+    },
+    /// Mine the salt for an ERC-4337 account factory like SimpleAccountFactory so the counterfactual account is vanity
+    Account {
+        // the account factory
+        #[arg(long, value_parser = parse_address)]
+        factory: [u8; 20],
+
+        // the account implementation the factory's proxies point at
+        #[arg(long, value_parser = parse_address)]
+        implementation: [u8; 20],
+
+        // type(ERC1967Proxy).creationCode as compiled into the factory (hex)
+        #[arg(long, value_parser = parse_hex)]
+        proxy_creation_code: ::std::vec::Vec<u8>,
+
+        // the account owner passed to getAddress/createAccount
+        #[arg(long, value_parser = parse_address, required_unless_present = "init_call")]
+        owner: Option<[u8; 20]>,
+
+        // calldata the proxy calls the implementation with (hex), instead of initialize(owner)
+        #[arg(long, value_parser = parse_hex, conflicts_with = "owner")]
+        init_call: Option<::std::vec::Vec<u8>>,
+    },
+    /// Find a deployer key whose CREATE at a given nonce lands on a vanity address
+    Create {
+        // deployer nonce of the deployment
+        #[arg(long, default_value_t = 0)]
+        nonce: u64,
+
+        // the deployer's own address must match the pattern too
+        #[arg(long, default_value_t = false)]
+        match_deployer: bool,
+    },
 }
 
 fn main() {
@@ -65,30 +366,65 @@ fn main() {
 
     let args = Args::parse();
 
-    let start_pattern = args.start_pattern.to_lowercase();
-    let end_pattern = args.end_pattern.to_lowercase();
-    let use_checksum = args.checksum;
+    // a lookup, not a search
+    if let Some(Mode::VerifyClone {
+        deployer,
+        implementation,
+        salt,
+        address,
+    }) = &args.mode
+    {
+        verify_clone(deployer, implementation, salt, address.as_ref());
+        return;
+    }
+
+    let use_checksum = !args.no_checksum;
     let step = args.step;
     let max_tries = args.max_tries;
     let log_interval = args.log_interval;
-    let min_zeros = args.min_zeros;
+    let min_zeros = args.min_zeros();
 
-    let regex_pattern = if !args.regex_pattern.is_empty() {
-        Some(Arc::new(
-            Regex::new(&args.regex_pattern).expect("Invalid regex pattern"),
-        ))
-    } else {
-        None
-    };
+    // reject malformed or unsatisfiable criteria before doing any work
+    let criteria = validate_args(&args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
 
-    // add a confirmation prompt
-    if !confirm_start(&args) {
-        println!("Operation cancelled by user.");
-        return;
-    }
+    let read = |path: &PathBuf| {
+        std::fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            std::process::exit(1);
+        })
+    };
+    let targets = args.targets.as_ref().map(|path| {
+        TargetSet::parse(&read(path))
+            .unwrap_or_else(|e| {
+                eprintln!("Invalid target in {}: {}", path.display(), e);
+                std::process::exit(1);
+            })
+            .retire(args.retire)
+    });
+    let targets = targets.or_else(|| {
+        let path = args.words.as_ref()?;
+        let (words, unspellable) = hexspeak::wordlist(&read(path), args.min_word_length);
+        println!(
+            "Words: {} hunted, {} not spellable in hex",
+            words.len().to_string().yellow(),
+            unspellable.to_string().yellow()
+        );
+        if words.is_empty() {
+            eprintln!(
+                "No word in {} spells at least {} hex characters",
+                path.display(),
+                args.min_word_length
+            );
+            std::process::exit(1);
+        }
+        Some(words.retire(args.retire))
+    });
 
     println!("Starting Vanity Address Generator 🧪");
-    println!("Prefix: {}", args.start_pattern.bright_green());
+    println!("Prefix: {}", args.start_pattern().bright_green());
     println!("Suffix: {}", args.end_pattern.bright_green());
     println!(
         "Checksum: {}",
@@ -98,243 +434,788 @@ fn main() {
             "❌".red()
         }
     );
+    println!("Case: {:?}", args.case);
+    println!("Strategy: {:?}", args.strategy);
     println!("Minimum Zeros: {}", min_zeros.to_string().yellow());
     println!("Step: {}", step.to_string().yellow());
     println!("Max Tries: {}", max_tries.to_string().yellow());
     println!("Log Interval (ms): {}", log_interval.to_string().yellow());
     println!("Regex Pattern: {}", args.regex_pattern.yellow());
+    if let Some(targets) = &targets {
+        println!(
+            "Targets: {}{}",
+            targets.len().to_string().yellow(),
+            if targets.retires() {
+                " (each retired once found)"
+            } else {
+                ""
+            }
+        );
+    }
 
-    let found = Arc::new(AtomicBool::new(false));
-    let result_map = Arc::new(DashMap::new());
-    let total_attempts = Arc::new(DashMap::new());
-    total_attempts.insert("attempts", 0u64);
+    let target = match &args.mode {
+        None => Target::Eoa,
+        Some(Mode::Create2 {
+            deployer,
+            init_code_hash,
+            salt_prefix,
+        })
+        | Some(Mode::Hook {
+            deployer,
+            init_code_hash,
+            salt_prefix,
+            ..
+        }) => Target::Create2(Create2::new(*deployer, *init_code_hash).salt_prefix(salt_prefix)),
+        Some(Mode::Clone {
+            deployer,
+            implementation,
+            salt_prefix,
+        }) => {
+            Target::Create2(Create2::clone_of(*deployer, implementation).salt_prefix(salt_prefix))
+        }
+        Some(Mode::VerifyClone { .. }) => unreachable!("handled before the search"),
+        Some(Mode::Create3 {
+            factory,
+            proxy_init_code_hash,
+            salt_prefix,
+        }) => {
+            let mut create3 = Create3::new(*factory).salt_prefix(salt_prefix);
+            if let Some(hash) = proxy_init_code_hash {
+                create3 = create3.proxy_init_code_hash(*hash);
+            }
+            Target::Create3(create3)
+        }
+        Some(Mode::CreateX {
+            init_code_hash,
+            sender,
+            chain_id,
+            factory,
+        }) => {
+            let mut createx = match init_code_hash {
+                Some(hash) => CreateX::create2(*hash),
+                None => CreateX::create3(),
+            };
+            if let Some(sender) = sender {
+                createx = createx.sender(*sender);
+            }
+            if let Some(chain_id) = chain_id {
+                createx = createx.chain_id(*chain_id);
+            }
+            if let Some(factory) = factory {
+                createx = createx.factory(*factory);
+            }
+            Target::CreateX(createx)
+        }
+        Some(Mode::Account {
+            factory,
+            implementation,
+            proxy_creation_code,
+            owner,
+            init_call,
+        }) => {
+            let init_call = match init_call {
+                Some(init_call) => init_call.clone(),
+                None => initialize_call(&owner.expect("clap requires an owner")),
+            };
+            println!("Init call: 0x{}", hex::encode(&init_call));
+            Target::SmartAccount(SmartAccount::new(
+                *factory,
+                proxy_creation_code,
+                *implementation,
+                &init_call,
+            ))
+        }
+        Some(Mode::Safe {
+            factory,
+            singleton,
+            proxy_creation_code,
+            owners,
+            threshold,
+            fallback_handler,
+            initializer,
+        }) => {
+            let initializer = match initializer {
+                Some(initializer) => initializer.clone(),
+                None => setup_initializer(owners, *threshold, fallback_handler.unwrap_or_default())
+                    .unwrap_or_else(|e| {
+                        eprintln!("--owners: {}", e);
+                        std::process::exit(1);
+                    }),
+            };
+            println!("Initializer: 0x{}", hex::encode(&initializer));
+            Target::Safe(Safe::new(
+                *factory,
+                proxy_creation_code,
+                *singleton,
+                &initializer,
+            ))
+        }
+        Some(Mode::Create {
+            nonce,
+            match_deployer,
+        }) => Target::Create(Create::new(*nonce).match_deployer(*match_deployer)),
+    };
+
+    let mut search = Search::new()
+        .target(target.clone())
+        .prefix(criteria.start)
+        .suffix(criteria.end)
+        .case(args.case)
+        .min_zeros(min_zeros)
+        .strategy(args.strategy)
+        .checksum(use_checksum)
+        .step(step)
+        .max_tries(max_tries);
+    if args.keep_hunting {
+        search = search.max_results(usize::MAX);
+    } else if let Some(max_results) = args.max_results {
+        search = search.max_results(max_results);
+    }
+    if let Some(timeout) = args.timeout {
+        search = search.timeout(timeout);
+    }
+    if let Some(stop_file) = &args.stop_file {
+        search = search.stop_file(stop_file);
+    }
+    if let Some(bytes) = args.leading_zero_bytes {
+        println!("Leading Zero Bytes: {}", bytes.to_string().yellow());
+        search = search.leading_zero_bytes(bytes);
+    }
+    if let Some(bytes) = args.min_zero_bytes {
+        println!("Minimum Zero Bytes: {}", bytes.to_string().yellow());
+        search = search.min_zero_bytes(bytes);
+    }
+    if let Some(bits) = criteria.bits {
+        println!(
+            "Bits: {} of 160 fixed",
+            bits.fixed_bits().to_string().yellow()
+        );
+        search = search.matcher(bits);
+    }
+    let hook = criteria.hook.is_some();
+    if let Some(bits) = criteria.hook {
+        println!(
+            "Hook flags: {}",
+            format!(
+                "0x{:04x}",
+                u16::from_be_bytes([bits.value()[18], bits.value()[19]])
+            )
+            .yellow()
+        );
+        search = search.matcher(bits);
+    }
+    if let Some(pattern) = &args.mask {
+        println!("Mask: {}", pattern.to_string().bright_green());
+        search = search.pattern(pattern);
+    }
+    if let Some(filter) = &args.filter {
+        println!("Filter: {}", filter.source().bright_green());
+        search = search.matcher(filter.clone());
+    }
+    if let Some(regex) = criteria.regex {
+        search = search.regex(regex);
+    }
+    if let Some(targets) = targets {
+        search = search.targets(targets);
+    }
+    for preset in &args.preset {
+        let probability = preset.probability();
+        println!(
+            "Preset {}: 1 in {} addresses",
+            preset.to_string().bright_green(),
+            format!("{:.0}", 1.0 / probability).yellow()
+        );
+        search = search.matcher(*preset);
+    }
+    if let Some(top) = args.top {
+        println!(
+            "Leaderboard: top {} by {:?}",
+            top.to_string().yellow(),
+            args.score
+        );
+        search = search.top(top, args.score);
+    }
+    print_case_difficulty(search.cased_letters());
+    let difficulty = search.difficulty();
+    print_difficulty(difficulty, &search);
+
+    // add a confirmation prompt
+    if !confirm_start(&args) {
+        println!("Operation cancelled by user.");
+        return;
+    }
+
+    let progress = search.progress();
+
+    // create 'gen' directory if it doesn't exist
+    std::fs::create_dir_all("gen").expect("Failed to create 'gen' directory");
+
+    // log startup
+    append_log(&format!(
+        "Starting hash_hunter with prefix: {}, suffix: {}",
+        args.start_pattern(),
+        args.end_pattern
+    ));
+
+    // save every hit as it arrives, so long runs keep what they find
+    let search = {
+        let target = target.clone();
+        search.on_result(move |result| {
+            report(result, &target, difficulty, hook);
+            append_log(&format!(
+                "Found match! Address: {}, Attempts: {}",
+                result.address, result.attempts
+            ));
+        })
+    };
 
     let start_time = Instant::now();
 
     let progress_bar = Arc::new(setup_progress_bar(max_tries));
 
+    let leaderboard = search.leaderboard();
+
     // start logs
     {
-        let total_attempts = Arc::clone(&total_attempts);
-        let found = Arc::clone(&found);
+        let progress = Arc::clone(&progress);
         let progress_bar = Arc::clone(&progress_bar);
+        let leaderboard = leaderboard.clone();
+        let target = target.clone();
         std::thread::spawn(move || {
             let mut last_attempts = 0u64;
-            while !found.load(Ordering::Relaxed) {
+            while !progress.is_finished() {
                 std::thread::sleep(Duration::from_millis(log_interval));
-                let attempts = total_attempts.get("attempts").map(|a| *a).unwrap_or(0);
+                let attempts = progress.attempts();
                 progress_bar.set_position(attempts);
 
                 // add rate calculation
                 let rate = (attempts - last_attempts) as f64 / (log_interval as f64 / 1000.0);
                 println!("Rate: {:.2} attempts/sec, Total: {}", rate, attempts);
                 last_attempts = attempts;
-            }
-        });
-    }
 
-    // check cores to determine cpu count, then create threads
-    let num_threads = num_cpus::get();
-
-    // create scope for threads
-    rayon::scope(|s| {
-        for _ in 0..num_threads {
-            let start_pattern = start_pattern.clone();
-            let end_pattern = end_pattern.clone();
-            let found = Arc::clone(&found);
-            let result_map = Arc::clone(&result_map);
-            let total_attempts = Arc::clone(&total_attempts);
-            let regex_pattern = regex_pattern.clone(); // clone the regex pattern for each thread
-            s.spawn(move |_| {
-                let secp = Secp256k1::new();
-                let mut rng = OsRng;
-                let mut local_attempts = 0u64;
-
-                let mut warmup = vec![];
-                for _ in 0..1000 {
-                    let sk = SecretKey::new(&mut rng);
-                    warmup.push(sk);
-                }
-                warmup.clear(); // Clear but keep capacity
-
-                while !found.load(Ordering::Relaxed)
-                    && total_attempts.get("attempts").map_or(0, |a| *a) < max_tries
-                {
-                    // key gen
-                    let secret_key = SecretKey::new(&mut rng);
-
-                    // compute pubkey
-                    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
-                    let serialized_pub = public_key.serialize_uncompressed();
-
-                    // compute the keccak-256 hash of the public key ( - first byte)
-                    let hash = Keccak256::digest(&serialized_pub[1..]);
-
-                    // take last 20 bytes as address
-                    let address = hex::encode(&hash[12..]);
-
-                    // apply checksum if enabled
-                    let final_address = if use_checksum {
-                        to_checksum_address(&address)
-                    } else {
-                        address.clone()
-                    };
-
-                    // check prefix, suffix, minimum zeros, and regex pattern
-                    let zero_count = final_address.matches('0').count();
-                    if final_address.starts_with(&start_pattern)
-                        && final_address.ends_with(&end_pattern)
-                        && zero_count >= min_zeros
-                        && regex_pattern
-                            .as_ref()
-                            .is_none_or(|re| re.is_match(&final_address))
-                    {
-                        // found a matching address
-                        let address_with_prefix = format!("0x{}", final_address);
-                        let priv_key_hex = hex::encode(secret_key.as_ref());
-
-                        // insert the result
-                        result_map.insert("result", VanityResult {
-                            address: address_with_prefix.clone(),
-                            priv_key: priv_key_hex.clone(),
-                            attempts: total_attempts.get("attempts").map(|a| *a).unwrap_or(0),
-                        });
-
-                        // signal other threads to stop
-                        found.store(true, Ordering::Relaxed);
-                        break;
-                    }
-
-                    // increment counters
-                    local_attempts += 1;
-                    if local_attempts >= step {
-                        total_attempts
-                            .entry("attempts")
-                            .and_modify(|a| *a += local_attempts);
-                        local_attempts = 0;
-                    }
+                // chance a hit should have turned up by now, and when it will
+                if let (Some(difficulty), None) = (difficulty, &leaderboard) {
+                    let elapsed = start_time.elapsed().as_secs_f64();
+                    println!(
+                        "Chance of a hit by now: {}% ({})",
+                        format_percent(difficulty.success_probability(attempts)),
+                        format_percentiles(&difficulty, attempts as f64 / elapsed, elapsed)
+                    );
                 }
 
-                // add remaining attempts
-                if local_attempts > 0 {
-                    total_attempts
-                        .entry("attempts")
-                        .and_modify(|a| *a += local_attempts);
+                // persist the board so a killed run keeps it
+                if let Some(leaderboard) = &leaderboard {
+                    save_leaderboard(leaderboard, &target);
                 }
-            });
-        }
-    });
+            }
+        });
+    }
 
-    // create 'gen' directory if it doesn't exist
-    std::fs::create_dir_all("gen").expect("Failed to create 'gen' directory");
+    let results = search.run();
 
     // update progress bar one last time
-    let final_attempts = total_attempts.get("attempts").map(|a| *a).unwrap_or(0);
+    let final_attempts = progress.attempts();
     progress_bar.set_position(final_attempts);
     progress_bar.finish_with_message("Search completed");
 
     // check if a result was found
-    if let Some(result) = result_map.get("result") {
-        println!("\n{}", "🌀 Address found! 🌀".bright_green().bold());
-        println!("Address: {}", result.address.bright_green());
-        println!("Private Key: {}", result.priv_key.yellow());
-        println!("Total attempts: {}", result.attempts.to_string().cyan());
-
-        // verify the generated address
-        if verify_address(&result.address, &result.priv_key) {
-            println!("{}", "Address verification: PASSED ✅".green());
-        } else {
-            println!("{}", "Address verification: FAILED ❌".red());
-            println!(
-                "{}",
-                "Warning: The generated address does not match the private key!"
-                    .red()
-                    .bold()
-            );
-        }
+    if let Some(leaderboard) = &leaderboard {
+        save_leaderboard(leaderboard, &target);
+        print_leaderboard(leaderboard);
+    } else if !results.is_empty() {
+        println!(
+            "\n{}",
+            format!("🌀 {} address(es) found 🌀", results.len())
+                .bright_green()
+                .bold()
+        );
 
-        // create a filename based on the public key
-        let filename = format!("gen/{}.json", result.address);
+        // create a success marker file
+        let found: String = results
+            .iter()
+            .map(|result| format!("Found address: {}\n", result.address))
+            .collect();
+        std::fs::write("gen/SUCCESS", found).expect("Failed to write success marker");
+    } else {
+        let reason = match progress.stop_reason() {
+            Some(StopReason::Timeout) => "Timed out",
+            Some(StopReason::StopFile) => "Stop file found",
+            Some(StopReason::Stopped) => "Stopped",
+            _ => "Maximum attempts reached",
+        };
+        println!(
+            "{}",
+            format!("{} without finding a matching address. 😭", reason).red()
+        );
+    }
 
-        // create a JSON object
-        let json_output = serde_json::json!({
-            "address": result.address,
-            "privateKey": result.priv_key,
-            "totalAttempts": result.attempts
-        });
+    let elapsed = start_time.elapsed();
+    println!(
+        "Total time elapsed: {:.2?} for {} attempts ⏱️",
+        elapsed,
+        final_attempts.to_string().cyan()
+    );
+}
 
-        // write to file
-        std::fs::write(
-            &filename,
-            serde_json::to_string_pretty(&json_output).unwrap(),
-        )
-        .expect("Unable to write to file");
+// print, verify and save one hit
+fn report(result: &VanityResult, target: &Target, difficulty: Option<Difficulty>, hook: bool) {
+    println!("\n{}", "🌀 Address found! 🌀".bright_green().bold());
+    println!("Address: {}", result.address.bright_green());
+    match &result.secret {
+        Secret::PrivateKey(key) => println!("Private Key: {}", key.yellow()),
+        Secret::Salt(salt) => match target {
+            Target::Safe(_) => println!("Salt nonce: {}", salt_nonce(salt).yellow()),
+            Target::SmartAccount(_) => println!("Salt: {}", salt_nonce(salt).yellow()),
+            _ => println!("Salt: {}", format!("0x{}", salt).yellow()),
+        },
+    }
+    if let (Some(deployer), Target::Create(create)) = (&result.deployer, target) {
+        println!("Deployer: {}", deployer.bright_green());
+        println!("Nonce: {}", create.nonce.to_string().yellow());
+    }
+    if let Some(matched) = &result.matched {
+        println!("Target: {}", matched.bright_green());
+    }
+    println!("Total attempts: {}", result.attempts.to_string().cyan());
+    let address = parse_address(&result.address).expect("search produced a bad address");
+    // measured against a random address, 319.06 gas: it averages only 0.078
+    // zero bytes, so one with none costs 0.94 more
+    let saving = calldata_gas_saving(&address);
+    let versus = if saving >= 0.0 {
+        format!("{} saved", format!("{:.2}", saving).green())
+    } else {
+        format!("costs {} more", format!("{:.2}", -saving).red())
+    };
+    println!(
+        "Calldata gas: {} ({} per use vs a random address)",
+        calldata_gas(&address).to_string().yellow(),
+        versus
+    );
+    let permissions = hook.then(|| {
+        hook_permissions(&address)
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+    });
+    if let Some(permissions) = &permissions {
+        println!(
+            "Hook permissions: {}",
+            permissions.join(", ").bright_green()
+        );
+    }
 
+    // verify the generated address
+    if target.verify(result) {
+        println!("{}", "Address verification: PASSED ✅".green());
+    } else {
+        println!("{}", "Address verification: FAILED ❌".red());
         println!(
             "{}",
-            format!(
-                "Address, private key, and attempt count saved to {} 💾",
-                filename
-            )
-            .bright_blue()
+            "Warning: The generated address does not match its key or salt!"
+                .red()
+                .bold()
         );
+    }
 
-        // add entropy estimation
-        print_entropy_estimation(&result.address);
-
-        let log_file = OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open("gen/hunter.log")
-            .expect("Failed to open log file");
+    // create a filename based on the public key
+    let filename = format!("gen/{}.json", result.address);
 
-        let mut log_writer = std::io::BufWriter::new(log_file);
+    // write to file
+    let mut json = result_json(result, target);
+    if let Some(permissions) = permissions {
+        json["hookPermissions"] = permissions.into();
+    }
+    std::fs::write(&filename, serde_json::to_string_pretty(&json).unwrap())
+        .expect("Unable to write to file");
 
-        // log startup
-        writeln!(
-            log_writer,
-            "[{}] Starting hash_hunter with prefix: {}, suffix: {}",
-            Utc::now(),
-            start_pattern,
-            end_pattern
-        )
-        .expect("Failed to write to log");
-        log_writer.flush().expect("Failed to flush log");
-
-        // when a match is found, log it
-        writeln!(
-            log_writer,
-            "[{}] Found match! Address: {}, Attempts: {}",
-            Utc::now(),
-            result.address,
-            result.attempts
+    println!(
+        "{}",
+        format!(
+            "Address, {}, and attempt count saved to {} 💾",
+            match result.secret {
+                Secret::PrivateKey(_) => "private key",
+                Secret::Salt(_) => "salt",
+            },
+            filename
         )
-        .expect("Failed to write to log");
-        log_writer.flush().expect("Failed to flush log");
+        .bright_blue()
+    );
 
-        // create a success marker file
-        std::fs::write(
-            "gen/SUCCESS",
-            format!("Found address: {}\n", result.address),
-        )
-        .expect("Failed to write success marker");
-    } else {
+    // how lucky the hunt was
+    if let Some(difficulty) = difficulty {
         println!(
-            "{}",
-            "Maximum attempts reached without finding a matching address. 😭".red()
+            "Found after {:.2}x the expected attempts",
+            result.attempts as f64 / difficulty.expected_attempts()
         );
     }
+}
 
-    let elapsed = start_time.elapsed();
+// print where a clone with this salt lands, and check it against `address`
+fn verify_clone(
+    deployer: &[u8; 20],
+    implementation: &[u8; 20],
+    salt: &[u8; 32],
+    address: Option<&[u8; 20]>,
+) {
+    let create2 = Create2::clone_of(*deployer, implementation);
+    let predicted = create2.address(salt);
     println!(
-        "Total time elapsed: {:.2?} for {} attempts ⏱️",
-        elapsed,
-        total_attempts
-            .get("attempts")
-            .map_or(0, |a| *a)
-            .to_string()
-            .cyan()
+        "Init code: 0x{}",
+        hex::encode(clone_init_code(implementation))
+    );
+    println!("Init code hash: 0x{}", hex::encode(create2.init_code_hash));
+    println!(
+        "Predicted address: {}",
+        format!("0x{}", to_checksum_address(&hex::encode(predicted))).bright_green()
+    );
+    match address {
+        Some(address) if *address == predicted => {
+            println!("{}", "Address verification: PASSED ✅".green());
+        }
+        Some(_) => {
+            println!("{}", "Address verification: FAILED ❌".red());
+            std::process::exit(1);
+        }
+        None => {}
+    }
+}
+
+// write the board, best first, to gen/leaderboard.json
+fn save_leaderboard(leaderboard: &Leaderboard, target: &Target) {
+    let entries: Vec<_> = leaderboard
+        .entries()
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let mut json = result_json(&entry.result, target);
+            json["rank"] = (i + 1).into();
+            json["score"] = entry.score.into();
+            json
+        })
+        .collect();
+    std::fs::write(
+        "gen/leaderboard.json",
+        serde_json::to_string_pretty(&entries).unwrap(),
+    )
+    .expect("Unable to write leaderboard");
+}
+
+fn print_leaderboard(leaderboard: &Leaderboard) {
+    let entries = leaderboard.entries();
+    println!(
+        "\n{}",
+        format!("🏆 Top {} by {:?} 🏆", entries.len(), leaderboard.scoring())
+            .bright_green()
+            .bold()
     );
+    for (i, entry) in entries.iter().enumerate() {
+        // counts print as whole numbers, the rarity ratio to 4 places
+        let score = match leaderboard.scoring() {
+            Scoring::Rarity => format!("{:.4}", entry.score),
+            _ => entry.score.to_string(),
+        };
+        println!(
+            "{:>3}. {:>8}  {}",
+            i + 1,
+            score,
+            entry.result.address.bright_green()
+        );
+    }
+    println!(
+        "{}",
+        "Keys and salts saved to gen/leaderboard.json 💾".bright_blue()
+    );
+}
+
+// append a timestamped line to gen/hunter.log
+fn append_log(line: &str) {
+    let mut log_file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("gen/hunter.log")
+        .expect("Failed to open log file");
+    writeln!(log_file, "[{}] {}", Utc::now(), line).expect("Failed to write to log");
+}
+
+// JSON written to gen/ for a result
+fn result_json(result: &VanityResult, target: &Target) -> serde_json::Value {
+    let mut json = match (&result.secret, target) {
+        (Secret::Salt(salt), Target::Create2(create2)) => serde_json::json!({
+            "address": result.address,
+            "salt": format!("0x{}", salt),
+            "deployer": format!("0x{}", hex::encode(create2.deployer)),
+            "initCodeHash": format!("0x{}", hex::encode(create2.init_code_hash)),
+            "totalAttempts": result.attempts
+        }),
+        (Secret::PrivateKey(key), Target::Create(create)) => serde_json::json!({
+            "address": result.address,
+            "privateKey": key,
+            "deployer": result.deployer,
+            "nonce": create.nonce,
+            "totalAttempts": result.attempts
+        }),
+        (Secret::Salt(salt), Target::Create3(create3)) => {
+            let proxy = create3.proxy(&parse_hash(salt).expect("search produced a bad salt"));
+            serde_json::json!({
+                "address": result.address,
+                "salt": format!("0x{}", salt),
+                "factory": format!("0x{}", hex::encode(create3.factory)),
+                "proxy": format!("0x{}", hex::encode(proxy)),
+                "proxyInitCodeHash": format!("0x{}", hex::encode(create3.proxy_init_code_hash)),
+                "totalAttempts": result.attempts
+            })
+        }
+        (Secret::Salt(salt), Target::CreateX(createx)) => {
+            let salt_bytes = parse_hash(salt).expect("search produced a bad salt");
+            let mut json = serde_json::json!({
+                "address": result.address,
+                "salt": format!("0x{}", salt),
+                "guardedSalt": format!("0x{}", hex::encode(createx.guarded_salt(&salt_bytes))),
+                "factory": format!("0x{}", hex::encode(createx.factory)),
+                "totalAttempts": result.attempts
+            });
+            match createx.init_code_hash {
+                Some(hash) => {
+                    json["function"] = "deployCreate2".into();
+                    json["initCodeHash"] = format!("0x{}", hex::encode(hash)).into();
+                }
+                None => {
+                    let proxy = createx.proxy(&salt_bytes).unwrap();
+                    json["function"] = "deployCreate3".into();
+                    json["proxy"] = format!("0x{}", hex::encode(proxy)).into();
+                }
+            }
+            if let Some(sender) = createx.sender {
+                json["sender"] = format!("0x{}", hex::encode(sender)).into();
+            }
+            if let Some(chain_id) = createx.chain_id {
+                json["chainId"] = chain_id.into();
+            }
+            json
+        }
+        (Secret::Salt(salt), Target::SmartAccount(account)) => serde_json::json!({
+            "address": result.address,
+            "salt": salt_nonce(salt),
+            "factory": format!("0x{}", hex::encode(account.factory)),
+            "implementation": format!("0x{}", hex::encode(account.implementation)),
+            "initCodeHash": format!("0x{}", hex::encode(account.init_code_hash)),
+            "totalAttempts": result.attempts
+        }),
+        (Secret::Salt(salt), Target::Safe(safe)) => serde_json::json!({
+            "address": result.address,
+            "saltNonce": salt_nonce(salt),
+            "factory": format!("0x{}", hex::encode(safe.factory)),
+            "singleton": format!("0x{}", hex::encode(safe.singleton)),
+            "initializerHash": format!("0x{}", hex::encode(safe.initializer_hash)),
+            "initCodeHash": format!("0x{}", hex::encode(safe.init_code_hash)),
+            "totalAttempts": result.attempts
+        }),
+        (Secret::Salt(salt), _) => serde_json::json!({
+            "address": result.address,
+            "salt": format!("0x{}", salt),
+            "totalAttempts": result.attempts
+        }),
+        (Secret::PrivateKey(key), _) => serde_json::json!({
+            "address": result.address,
+            "privateKey": key,
+            "totalAttempts": result.attempts
+        }),
+    };
+    if let Some(matched) = &result.matched {
+        json["target"] = matched.as_str().into();
+    }
+    let address = parse_address(&result.address).expect("search produced a bad address");
+    json["calldataGas"] = calldata_gas(&address).into();
+    json["calldataGasSaving"] = calldata_gas_saving(&address).into();
+    json
+}
+
+// criteria from the arguments, checked and ready for the search
+struct Criteria<'a> {
+    // prefix and suffix without 0x
+    start: &'a str,
+    end: &'a str,
+    regex: Option<Regex>,
+    bits: Option<BitMask>,
+    // v4 hook flags, for the hook subcommand
+    hook: Option<BitMask>,
+}
+
+// check the criteria up front, naming the argument at fault
+fn validate_args(args: &Args) -> Result<Criteria<'_>, String> {
+    let start = validate::hex_pattern(args.start_pattern())
+        .map_err(|e| format!("--start-pattern: {}", e))?;
+    let end =
+        validate::hex_pattern(&args.end_pattern).map_err(|e| format!("--end-pattern: {}", e))?;
+    let end_at = 40 - end.len();
+    if let Some(i) = validate::conflict(start, 0, end, end_at, args.case) {
+        return Err(format!(
+            "--start-pattern and --end-pattern overlap at character {} and disagree: '{}' vs '{}'",
+            i + 1,
+            &start[i..i + 1],
+            &end[i - end_at..i - end_at + 1]
+        ));
+    }
+    let mut mask = NibbleMask::prefix(start)
+        .merge(&NibbleMask::suffix(end))
+        .expect("checked for conflicts");
+
+    if let Some(bytes) = args.leading_zero_bytes {
+        if bytes > 20 {
+            return Err(format!(
+                "--leading-zero-bytes: {} is more than the 20 bytes in an address",
+                bytes
+            ));
+        }
+        let zeros = "00".repeat(bytes);
+        for (flag, pattern, at) in [
+            ("--start-pattern", start, 0),
+            ("--end-pattern", end, end_at),
+        ] {
+            if let Some(i) = validate::conflict(&zeros, 0, pattern, at, Case::Insensitive) {
+                return Err(format!(
+                    "--leading-zero-bytes: {} zero bytes need character {} to be '0', but {} has '{}'",
+                    bytes,
+                    i + 1,
+                    flag,
+                    &pattern[i - at..i - at + 1]
+                ));
+            }
+        }
+        mask = mask
+            .merge(&NibbleMask::prefix(&zeros))
+            .expect("checked for conflicts");
+    }
+    if let Some(pattern) = &args.mask {
+        let fixed = pattern.fixed();
+        if let Some(i) = (0..40)
+            .find(|&i| matches!((mask.nibble(i), fixed.nibble(i)), (Some(a), Some(b)) if a != b))
+        {
+            return Err(format!(
+                "--mask: character {} is '{:x}', but the prefix, suffix or zero bytes pin it to '{:x}'",
+                i + 1,
+                fixed.nibble(i).unwrap(),
+                mask.nibble(i).unwrap()
+            ));
+        }
+        mask = mask.merge(fixed).expect("checked for conflicts");
+    }
+    validate::min_zeros(&mask, args.min_zeros()).map_err(|e| format!("--min-zeros: {}", e))?;
+    if let Some(bytes) = args.min_zero_bytes {
+        validate::min_zero_bytes(&mask, bytes).map_err(|e| format!("--min-zero-bytes: {}", e))?;
+    }
+
+    let regex = if args.regex_pattern.is_empty() {
+        None
+    } else {
+        let regex =
+            Regex::new(&args.regex_pattern).map_err(|e| format!("--regex-pattern: {}", e))?;
+        validate::regex(&regex, args.case).map_err(|e| format!("--regex-pattern: {}", e))?;
+        Some(regex)
+    };
+    let bits = match args.bitmask {
+        Some(bitmask) => {
+            let bits = BitMask::new(bitmask, args.bitvalue.unwrap_or_default())
+                .map_err(|e| format!("--bitvalue: {}", e))?;
+            if let Some(i) = validate::mask_bits(&mask, &bits) {
+                return Err(format!(
+                    "--bitmask: character {} is pinned to '{:x}', which the bit value rules out",
+                    i + 1,
+                    mask.nibble(i).unwrap()
+                ));
+            }
+            Some(bits)
+        }
+        None => None,
+    };
+    let hook = match &args.mode {
+        Some(Mode::Hook { permissions, .. }) => {
+            let flags = hook_flags(permissions).map_err(|e| format!("--permissions: {}", e))?;
+            let hook = hook_bits(flags);
+            if let Some(i) = validate::mask_bits(&mask, &hook) {
+                return Err(format!(
+                    "--permissions: character {} is pinned to '{:x}', but the hook flags need it to be '{:x}'",
+                    i + 1,
+                    mask.nibble(i).unwrap(),
+                    hook.value()[i / 2] >> (if i.is_multiple_of(2) { 4 } else { 0 }) & 0x0F
+                ));
+            }
+            if let Some(bit) = bits.as_ref().and_then(|bits| validate::bits(bits, &hook)) {
+                return Err(format!(
+                    "--bitmask: bit {} is fixed to {}, but the hook flags need it to be {}",
+                    bit,
+                    1 - (hook.value()[19 - bit / 8] >> (bit % 8) & 1),
+                    hook.value()[19 - bit / 8] >> (bit % 8) & 1
+                ));
+            }
+            Some(hook)
+        }
+        _ => None,
+    };
+    for preset in &args.preset {
+        validate::preset(preset, &mask).map_err(|e| format!("--preset: {}", e))?;
+    }
+    Ok(Criteria {
+        start,
+        end,
+        regex,
+        bits,
+        hook,
+    })
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {}", value))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("unknown duration unit: {}", unit)),
+    };
+    number
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration too long: {}", value))
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|e| format!("invalid hex: {}", e))
+}
+
+fn parse_address(value: &str) -> Result<[u8; 20], String> {
+    parse_hex(value)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 20 bytes, got {}", bytes.len()))
+}
+
+fn parse_hash(value: &str) -> Result<[u8; 32], String> {
+    parse_hex(value)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()))
+}
+
+// a mined salt as a decimal uint256 argument (saltNonce, salt)
+fn salt_nonce(salt: &str) -> String {
+    uint256_decimal(&parse_hash(salt).expect("search produced a bad salt"))
+}
+
+fn parse_salt_prefix(value: &str) -> Result<Vec<u8>, String> {
+    let bytes = parse_hex(value)?;
+    if bytes.len() > MAX_SALT_PREFIX {
+        return Err(format!(
+            "at most {} bytes, got {}",
+            MAX_SALT_PREFIX,
+            bytes.len()
+        ));
+    }
+    Ok(bytes)
 }
 
 fn print_startup_screen() {
@@ -359,45 +1240,6 @@ fn print_startup_screen() {
     println!("\n");
 }
 
-fn verify_address(address: &str, private_key: &str) -> bool {
-    let secp = Secp256k1::new();
-    let secret_key = SecretKey::from_slice(&hex::decode(private_key).unwrap()).unwrap();
-    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
-
-    let serialized_pub = public_key.serialize_uncompressed();
-    let hash = Keccak256::digest(&serialized_pub[1..]);
-    let generated_address = format!("0x{}", hex::encode(&hash[12..]));
-
-    address.to_lowercase() == generated_address.to_lowercase()
-}
-
-// converts an eth address to its EIP-55 checksummed version
-fn to_checksum_address(address: &str) -> String {
-    let address = address.to_lowercase();
-    let hash = Keccak256::digest(address.as_bytes());
-    let mut checksum_address = String::with_capacity(40);
-
-    for (i, c) in address.chars().enumerate() {
-        if c.is_ascii_digit() {
-            checksum_address.push(c);
-        } else {
-            let hash_byte = hash[i / 2];
-            let nibble = if i % 2 == 0 {
-                hash_byte >> 4
-            } else {
-                hash_byte & 0x0F
-            };
-            if nibble >= 8 {
-                checksum_address.push(c.to_ascii_uppercase());
-            } else {
-                checksum_address.push(c);
-            }
-        }
-    }
-    // ggez
-    checksum_address
-}
-
 fn confirm_start(args: &Args) -> bool {
     if args.skip_confirmation {
         return true;
@@ -425,17 +1267,101 @@ fn setup_progress_bar(max_tries: u64) -> ProgressBar {
     pb
 }
 
-fn print_entropy_estimation(address: &str) {
-    let address_without_prefix = address.trim_start_matches("0x");
-    let entropy_bits = address_without_prefix.len() * 4; // each hex character represents 4 bits
-    println!("Estimated entropy: {} bits", entropy_bits);
+fn print_case_difficulty(casing: &ChecksumCase) {
+    if casing.cased_letters() == 0 {
+        return;
+    }
 
-    let years_to_crack = calculate_years_to_crack(entropy_bits);
-    println!("Estimated time to crack: {:.2e} years", years_to_crack);
+    // each cased letter must land on the right side of a checksum hash nibble
+    let letters: Vec<String> = casing
+        .letters()
+        .iter()
+        .map(|&(position, upper)| {
+            format!(
+                "#{} {} (x2)",
+                position + 1,
+                if upper { "upper" } else { "lower" }
+            )
+        })
+        .collect();
+    println!("Cased letters: {}", letters.join(", ").yellow());
+    println!(
+        "Casing multiplies expected attempts by {}",
+        format!("{}", 1u128 << casing.cased_letters().min(127)).yellow()
+    );
 }
 
-fn calculate_years_to_crack(entropy_bits: usize) -> f64 {
-    let guesses_per_second = 1e12; // assume 1 trillion guesses per second
-    let seconds_to_crack = 2f64.powi(entropy_bits as i32) / guesses_per_second;
-    seconds_to_crack / (365.25 * 24.0 * 60.0 * 60.0)
+// odds of the criteria and, at a briefly measured rate, how long they take
+fn print_difficulty(difficulty: Option<Difficulty>, search: &Search) {
+    let Some(difficulty) = difficulty else {
+        println!(
+            "{}",
+            "Difficulty: unknown for a regex, filter or target file".bright_black()
+        );
+        return;
+    };
+    println!(
+        "Difficulty: {} bits, 1 in {} addresses",
+        format!("{:.1}", difficulty.bits()).yellow(),
+        format!("{:.0}", difficulty.expected_attempts()).yellow()
+    );
+
+    println!("Measuring hash rate...");
+    // the configured search itself, so every check and hash it makes counts
+    let rate = search.measure_rate(Duration::from_secs(1));
+    println!(
+        "Rate: {:.2} attempts/sec, expected time {}",
+        rate,
+        format_seconds(difficulty.expected_attempts() / rate).yellow()
+    );
+    println!(
+        "Chance of a hit: {}",
+        format_percentiles(&difficulty, rate, 0.0)
+    );
+}
+
+// time left, `elapsed` seconds in, until each standard chance of a hit
+fn format_percentiles(difficulty: &Difficulty, rate: f64, elapsed: f64) -> String {
+    Difficulty::CONFIDENCES
+        .iter()
+        .map(|&confidence| {
+            let seconds = difficulty.seconds_for(confidence, rate) - elapsed;
+            if seconds <= 0.0 {
+                format!("{:.0}% passed", confidence * 100.0)
+            } else {
+                format!("{:.0}% in {}", confidence * 100.0, format_seconds(seconds))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// percent to one place, or in scientific notation when that would read 0.0
+fn format_percent(probability: f64) -> String {
+    let percent = probability * 100.0;
+    if percent == 0.0 || percent >= 0.05 {
+        format!("{:.1}", percent)
+    } else {
+        format!("{:.1e}", percent)
+    }
+}
+
+// compact human duration: 45s, 12.5m, 3.2h, 4.1d, 2.3e9y
+fn format_seconds(seconds: f64) -> String {
+    const YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+    if !seconds.is_finite() {
+        "never".to_string()
+    } else if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else if seconds < 3600.0 {
+        format!("{:.1}m", seconds / 60.0)
+    } else if seconds < 86_400.0 {
+        format!("{:.1}h", seconds / 3600.0)
+    } else if seconds < YEAR {
+        format!("{:.1}d", seconds / 86_400.0)
+    } else if seconds < 1e6 * YEAR {
+        format!("{:.1}y", seconds / YEAR)
+    } else {
+        format!("{:.1e}y", seconds / YEAR)
+    }
 }
@@ -0,0 +1,494 @@
+use regex::Regex;
+use sha3::{Digest, Keccak256};
+
+/// How letters in a pattern are compared against an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Case {
+    /// Compare against the lowercase hex; `DeAd` and `dead` are the same.
+    #[default]
+    Insensitive,
+    /// Letters must also match the address's EIP-55 checksum casing. Every
+    /// cased letter halves the odds, on top of the 1-in-16 for the nibble.
+    Exact,
+}
+
+/// A condition a candidate address must satisfy.
+///
+/// Matchers see the raw 20 address bytes, so the hot loop never hex-encodes
+/// or checksums a candidate that is going to be rejected anyway. Closures of
+/// the right shape are matchers too, so custom criteria need no new types:
+///
+/// ```
+/// use hash_hunter::matcher::{Matcher, NibbleMask, all};
+///
+/// let m = all(vec![
+///     Box::new(NibbleMask::prefix("00")),
+///     Box::new(|address: &[u8; 20]| address[19] == 0xaa),
+/// ]);
+/// let mut address = [0u8; 20];
+/// address[19] = 0xaa;
+/// assert!(m.matches(&address));
+/// ```
+pub trait Matcher: Send + Sync {
+    fn matches(&self, address: &[u8; 20]) -> bool;
+
+    /// Chance that a uniformly random address matches, if known.
+    fn probability(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl<F> Matcher for F
+where
+    F: Fn(&[u8; 20]) -> bool + Send + Sync,
+{
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self(address)
+    }
+}
+
+impl Matcher for Box<dyn Matcher> {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        (**self).matches(address)
+    }
+
+    fn probability(&self) -> Option<f64> {
+        (**self).probability()
+    }
+}
+
+/// Lowercase hex of an address, encoded on the stack.
+pub fn hex_lower(address: &[u8; 20]) -> [u8; 40] {
+    let mut out = [0u8; 40];
+    hex::encode_to_slice(address, &mut out).unwrap();
+    out
+}
+
+/// EIP-55 checksummed hex of an address, encoded on the stack.
+pub fn hex_checksummed(address: &[u8; 20]) -> [u8; 40] {
+    let mut out = hex_lower(address);
+    let hash = Keccak256::digest(out);
+    for (i, c) in out.iter_mut().enumerate() {
+        if c.is_ascii_alphabetic() && checksum_nibble(&hash, i) >= 8 {
+            c.make_ascii_uppercase();
+        }
+    }
+    out
+}
+
+fn checksum_nibble(hash: &[u8], position: usize) -> u8 {
+    if position.is_multiple_of(2) {
+        hash[position / 2] >> 4
+    } else {
+        hash[position / 2] & 0x0F
+    }
+}
+
+/// Number of `0` nibbles in the address.
+pub fn zero_nibbles(address: &[u8; 20]) -> usize {
+    address
+        .iter()
+        .map(|b| (b >> 4 == 0) as usize + (b & 0x0F == 0) as usize)
+        .sum()
+}
+
+/// Fixed nibbles at fixed positions, compiled to per-byte mask and value
+/// tables: an address matches when `address[i] & mask[i] == value[i]` for
+/// every byte. Prefixes, suffixes and anything else anchored at known
+/// positions merge into one mask and are checked together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NibbleMask {
+    mask: [u8; 20],
+    value: [u8; 20],
+    // bytes outside [start, end) are unconstrained
+    start: usize,
+    end: usize,
+}
+
+impl NibbleMask {
+    /// Address starts with the given hex (no `0x`, any casing).
+    ///
+    /// # Panics
+    ///
+    /// If `pattern` is not hex or is longer than 40 characters.
+    pub fn prefix(pattern: &str) -> Self {
+        Self::at(0, pattern)
+    }
+
+    /// Address ends with the given hex.
+    ///
+    /// # Panics
+    ///
+    /// If `pattern` is not hex or is longer than 40 characters.
+    pub fn suffix(pattern: &str) -> Self {
+        assert!(pattern.len() <= 40, "pattern longer than an address");
+        Self::at(40 - pattern.len(), pattern)
+    }
+
+    /// The hex in `pattern` starts at nibble `offset` (0 is the first
+    /// character after `0x`).
+    ///
+    /// # Panics
+    ///
+    /// If `pattern` is not hex or runs past the 40th nibble.
+    pub fn at(offset: usize, pattern: &str) -> Self {
+        assert!(
+            offset + pattern.len() <= 40,
+            "pattern runs past the address"
+        );
+
+        let mut out = Self::default();
+        for (i, c) in pattern.chars().enumerate() {
+            let nibble = c.to_digit(16).expect("pattern must be hex") as u8;
+            out.set_nibble(offset + i, nibble);
+        }
+        out.shrink();
+        out
+    }
+
+    fn set_nibble(&mut self, position: usize, nibble: u8) {
+        let shift = if position.is_multiple_of(2) { 4 } else { 0 };
+        self.mask[position / 2] |= 0x0F << shift;
+        self.value[position / 2] |= nibble << shift;
+    }
+
+    fn shrink(&mut self) {
+        self.start = self.mask.iter().position(|&m| m != 0).unwrap_or(0);
+        self.end = self.mask.iter().rposition(|&m| m != 0).map_or(0, |i| i + 1);
+    }
+
+    /// Both masks at once, or `None` if they fix some nibble to different
+    /// values.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        let mut out = Self::default();
+        for i in 0..20 {
+            let shared = self.mask[i] & other.mask[i];
+            if self.value[i] & shared != other.value[i] & shared {
+                return None;
+            }
+            out.mask[i] = self.mask[i] | other.mask[i];
+            out.value[i] = self.value[i] | other.value[i];
+        }
+        out.shrink();
+        Some(out)
+    }
+
+    /// Number of nibbles the mask pins down.
+    pub fn fixed_nibbles(&self) -> usize {
+        self.mask.iter().map(|m| m.count_ones() as usize / 4).sum()
+    }
+
+    /// The value pinned at nibble `position`, if any.
+    pub fn nibble(&self, position: usize) -> Option<u8> {
+        let shift = if position.is_multiple_of(2) { 4 } else { 0 };
+        (self.mask[position / 2] >> shift & 0x0F != 0)
+            .then(|| self.value[position / 2] >> shift & 0x0F)
+    }
+
+    /// Whether the mask accepts every address.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl Matcher for NibbleMask {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        (self.start..self.end).all(|i| address[i] & self.mask[i] == self.value[i])
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(16f64.powi(-(self.fixed_nibbles() as i32)))
+    }
+}
+
+/// Letters at fixed positions carry a given EIP-55 casing.
+///
+/// Only the casing is checked: pair it with a [`NibbleMask`] for the same
+/// pattern, which rules out addresses holding a digit where the letter goes
+/// and is far cheaper to test first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumCase {
+    // (nibble position, uppercase)
+    letters: Vec<(usize, bool)>,
+}
+
+impl ChecksumCase {
+    pub const fn new() -> Self {
+        Self {
+            letters: Vec::new(),
+        }
+    }
+
+    /// Casing of the letters in `pattern` placed at nibble `offset`.
+    pub fn at(offset: usize, pattern: &str) -> Self {
+        let letters = pattern
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| c.is_ascii_alphabetic())
+            .map(|(i, c)| (offset + i, c.is_ascii_uppercase()))
+            .collect();
+        Self { letters }
+    }
+
+    pub fn prefix(pattern: &str) -> Self {
+        Self::at(0, pattern)
+    }
+
+    pub fn suffix(pattern: &str) -> Self {
+        Self::at(40usize.saturating_sub(pattern.len()), pattern)
+    }
+
+    /// Adds the letters of `other`.
+    pub fn extend(&mut self, other: &Self) {
+        self.letters.extend_from_slice(&other.letters);
+    }
+
+    /// Number of cased letters; each doubles the expected attempts.
+    pub fn cased_letters(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// The (nibble position, uppercase) pairs being enforced.
+    pub fn letters(&self) -> &[(usize, bool)] {
+        &self.letters
+    }
+}
+
+impl Matcher for ChecksumCase {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        if self.letters.is_empty() {
+            return true;
+        }
+        let hash = Keccak256::digest(hex_lower(address));
+        self.letters
+            .iter()
+            .all(|&(position, upper)| (checksum_nibble(&hash, position) >= 8) == upper)
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(0.5f64.powi(self.letters.len() as i32))
+    }
+}
+
+/// Address holds at least this many `0` characters anywhere.
+#[derive(Debug, Clone, Copy)]
+pub struct MinZeros(pub usize);
+
+impl Matcher for MinZeros {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        zero_nibbles(address) >= self.0
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(binomial_tail(40, 1.0 / 16.0, self.0))
+    }
+}
+
+/// Address starts with at least this many `0x00` bytes, the ones calldata
+/// and packed encodings can drop.
+#[derive(Debug, Clone, Copy)]
+pub struct LeadingZeroBytes(pub usize);
+
+impl Matcher for LeadingZeroBytes {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.0 <= 20 && address[..self.0].iter().all(|&b| b == 0)
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(256f64.powi(-(self.0 as i32)))
+    }
+}
+
+/// Address holds at least this many `0x00` bytes anywhere.
+#[derive(Debug, Clone, Copy)]
+pub struct MinZeroBytes(pub usize);
+
+impl Matcher for MinZeroBytes {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        address.iter().filter(|&&b| b == 0).count() >= self.0
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(binomial_tail(20, 1.0 / 256.0, self.0))
+    }
+}
+
+/// Chance of at least `k` successes in `n` independent trials of
+/// probability `p` (below 1).
+pub fn binomial_tail(n: usize, p: f64, k: usize) -> f64 {
+    if k == 0 {
+        return 1.0;
+    }
+    // C(n, i) p^i (1-p)^(n-i), built up term by term; summing the tail
+    // directly keeps tiny probabilities accurate
+    let mut term = (1.0 - p).powi(n as i32);
+    let mut tail = 0.0;
+    for i in 0..=n {
+        if i >= k {
+            tail += term;
+        }
+        term *= (n - i) as f64 / (i + 1) as f64 * p / (1.0 - p);
+    }
+    tail
+}
+
+/// Chosen bits of the 160-bit address hold chosen values:
+/// `address & mask == value`, checked on the bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitMask {
+    mask: [u8; 20],
+    value: [u8; 20],
+}
+
+impl BitMask {
+    /// Fails if `value` sets a bit outside `mask`, which no address could
+    /// match.
+    pub fn new(mask: [u8; 20], value: [u8; 20]) -> Result<Self, String> {
+        if let Some(bit) = (0..160).find(|&bit| {
+            let (byte, shift) = (19 - bit / 8, bit % 8);
+            value[byte] >> shift & 1 == 1 && mask[byte] >> shift & 1 == 0
+        }) {
+            return Err(format!("value sets bit {}, which the mask leaves out", bit));
+        }
+        Ok(Self { mask, value })
+    }
+
+    pub fn mask(&self) -> &[u8; 20] {
+        &self.mask
+    }
+
+    pub fn value(&self) -> &[u8; 20] {
+        &self.value
+    }
+
+    /// Number of bits the mask fixes.
+    pub fn fixed_bits(&self) -> u32 {
+        self.mask.iter().map(|b| b.count_ones()).sum()
+    }
+}
+
+impl Matcher for BitMask {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        address
+            .iter()
+            .zip(&self.mask)
+            .zip(&self.value)
+            .all(|((a, m), v)| a & m == *v)
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(0.5f64.powi(self.fixed_bits() as i32))
+    }
+}
+
+/// A 160-bit literal as address bytes: `0x` hex or `0b` binary, with
+/// underscores allowed and short literals counting from the lowest bit,
+/// like a number.
+///
+/// ```
+/// use hash_hunter::matcher::parse_bits;
+///
+/// let bits = parse_bits("0b1010_0001").unwrap();
+/// assert_eq!(bits[19], 0xa1);
+/// assert_eq!(parse_bits("0x1ff").unwrap()[18..], [0x01, 0xff]);
+/// ```
+pub fn parse_bits(literal: &str) -> Result<[u8; 20], String> {
+    let digits: String = literal.chars().filter(|&c| c != '_').collect();
+    let (radix, digits) = if let Some(binary) = digits.strip_prefix("0b") {
+        (2, binary)
+    } else {
+        (16, digits.strip_prefix("0x").unwrap_or(&digits))
+    };
+    if digits.is_empty() {
+        return Err(format!("{} has no digits", literal));
+    }
+    let bits_per_digit = if radix == 2 { 1 } else { 4 };
+    if digits.len() * bits_per_digit > 160 {
+        return Err(format!("{} is wider than 160 bits", literal));
+    }
+
+    let mut out = [0u8; 20];
+    for (i, c) in digits.chars().rev().enumerate() {
+        let digit = c
+            .to_digit(radix)
+            .ok_or_else(|| format!("'{}' is not a base-{} digit in {}", c, radix, literal))?
+            as u8;
+        let bit = i * bits_per_digit;
+        out[19 - bit / 8] |= digit << (bit % 8);
+    }
+    Ok(out)
+}
+
+/// The hex of the address matches a regex: lowercase, or in its EIP-55
+/// casing under [`Case::Exact`].
+#[derive(Debug, Clone)]
+pub struct RegexMatcher {
+    regex: Regex,
+    case: Case,
+}
+
+impl RegexMatcher {
+    pub fn new(regex: Regex, case: Case) -> Self {
+        Self { regex, case }
+    }
+}
+
+impl Matcher for RegexMatcher {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        let hex = match self.case {
+            Case::Insensitive => hex_lower(address),
+            Case::Exact => hex_checksummed(address),
+        };
+        self.regex.is_match(std::str::from_utf8(&hex).unwrap())
+    }
+}
+
+/// Every inner matcher accepts; an empty `All` accepts everything.
+#[derive(Default)]
+pub struct All(pub Vec<Box<dyn Matcher>>);
+
+impl Matcher for All {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.0.iter().all(|m| m.matches(address))
+    }
+
+    /// The product of the inner probabilities, treating them as independent.
+    fn probability(&self) -> Option<f64> {
+        self.0.iter().map(|m| m.probability()).product()
+    }
+}
+
+/// At least one inner matcher accepts.
+#[derive(Default)]
+pub struct Any(pub Vec<Box<dyn Matcher>>);
+
+impl Matcher for Any {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.0.iter().any(|m| m.matches(address))
+    }
+}
+
+/// Inverts the inner matcher.
+pub struct Not(pub Box<dyn Matcher>);
+
+impl Matcher for Not {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        !self.0.matches(address)
+    }
+
+    fn probability(&self) -> Option<f64> {
+        self.0.probability().map(|p| 1.0 - p)
+    }
+}
+
+pub fn all(matchers: Vec<Box<dyn Matcher>>) -> All {
+    All(matchers)
+}
+
+pub fn any(matchers: Vec<Box<dyn Matcher>>) -> Any {
+    Any(matchers)
+}
+
+pub fn not(matcher: impl Matcher + 'static) -> Not {
+    Not(Box::new(matcher))
+}
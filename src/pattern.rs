@@ -0,0 +1,250 @@
+use crate::matcher::{Matcher, NibbleMask};
+use std::fmt;
+use std::str::FromStr;
+
+/// A positional mask over all 40 characters of an address.
+///
+/// Written one position per character, case-insensitive, with an optional
+/// `0x`:
+///
+/// - a hex digit pins that character
+/// - `?` accepts any character
+/// - `[0-7]`, `[ace]` or `[^0]` accepts a class of characters
+/// - a letter from `g` to `z` is a variable: every position holding the same
+///   letter must hold the same character
+/// - one `*` stands for as many `?` as it takes to reach 40
+///
+/// So `????dead*` is `dead` at offset 4, and `wxyz*wxyz` has identical first
+/// and last four characters. Pinned characters compile into a [`NibbleMask`]
+/// for the fast path; classes and variables are checked after it.
+///
+/// ```
+/// use hash_hunter::pattern::Pattern;
+/// use hash_hunter::Matcher;
+///
+/// let pattern: Pattern = "????dead*[0-3]".parse().unwrap();
+/// let mut address = [0u8; 20];
+/// address[2..4].copy_from_slice(&[0xde, 0xad]);
+/// address[19] = 0x12;
+/// assert!(pattern.matches(&address));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pattern {
+    fixed: NibbleMask,
+    // (position, bit n set if nibble n is accepted)
+    classes: Vec<(usize, u16)>,
+    // positions that must all hold the same nibble
+    variables: Vec<Vec<usize>>,
+}
+
+impl Pattern {
+    /// The pinned characters.
+    pub fn fixed(&self) -> &NibbleMask {
+        &self.fixed
+    }
+
+    /// The same pattern without its pinned characters, for pairing with a
+    /// search that already checks [`Pattern::fixed`].
+    pub fn unpinned(&self) -> Self {
+        Self {
+            fixed: NibbleMask::default(),
+            ..self.clone()
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let body = pattern.strip_prefix("0x").unwrap_or(pattern);
+
+        // one entry per position, None for the * gap
+        let mut positions: Vec<Option<Position>> = Vec::new();
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            let position = match c.to_ascii_lowercase() {
+                '?' => Position::Any,
+                '*' if positions.contains(&None) => {
+                    return Err("only one * is allowed".to_string());
+                }
+                '*' => {
+                    positions.push(None);
+                    continue;
+                }
+                '[' => {
+                    let mut class = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => class.push(c),
+                            None => return Err(format!("[{} is missing its ]", class)),
+                        }
+                    }
+                    Position::Class(parse_class(&class)?)
+                }
+                c @ ('0'..='9' | 'a'..='f') => Position::Fixed(c.to_digit(16).unwrap() as u8),
+                c @ 'g'..='z' => Position::Variable(c),
+                c => return Err(format!("'{}' is not a hex digit, ?, *, [class] or g-z", c)),
+            };
+            positions.push(Some(position));
+        }
+
+        let len = positions.len() - positions.contains(&None) as usize;
+        let positions: Vec<Position> = match positions.iter().position(Option::is_none) {
+            Some(gap) if len <= 40 => {
+                let fill = std::iter::repeat_n(Some(Position::Any), 40 - len);
+                positions.splice(gap..=gap, fill);
+                positions.into_iter().flatten().collect()
+            }
+            None if len == 40 => positions.into_iter().flatten().collect(),
+            _ => {
+                return Err(format!(
+                    "{} positions, but an address has 40 (use * to fill the rest)",
+                    len
+                ));
+            }
+        };
+
+        let mut out = Self::default();
+        let mut letters: Vec<(char, Vec<usize>)> = Vec::new();
+        for (i, position) in positions.into_iter().enumerate() {
+            match position {
+                Position::Any => {}
+                Position::Fixed(nibble) => {
+                    out.fixed = out
+                        .fixed
+                        .merge(&NibbleMask::at(i, &format!("{:x}", nibble)))
+                        .unwrap();
+                }
+                // a one-character class is just a pinned character
+                Position::Class(set) if set.count_ones() == 1 => {
+                    let nibble = set.trailing_zeros();
+                    out.fixed = out
+                        .fixed
+                        .merge(&NibbleMask::at(i, &format!("{:x}", nibble)))
+                        .unwrap();
+                }
+                Position::Class(u16::MAX) => {}
+                Position::Class(set) => out.classes.push((i, set)),
+                Position::Variable(letter) => {
+                    match letters.iter_mut().find(|(l, _)| *l == letter) {
+                        Some((_, group)) => group.push(i),
+                        None => letters.push((letter, vec![i])),
+                    }
+                }
+            }
+        }
+        out.variables = letters
+            .into_iter()
+            .map(|(_, group)| group)
+            .filter(|group| group.len() > 1)
+            .collect();
+        Ok(out)
+    }
+}
+
+// all 40 positions written out, variables renamed from g in order
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..40 {
+            if let Some(nibble) = self.fixed.nibble(i) {
+                write!(f, "{:x}", nibble)?;
+            } else if let Some(&(_, set)) = self.classes.iter().find(|(at, _)| *at == i) {
+                let members: String = (0..16u32)
+                    .filter(|n| set >> n & 1 == 1)
+                    .map(|n| char::from_digit(n, 16).unwrap())
+                    .collect();
+                write!(f, "[{}]", members)?;
+            } else if let Some(v) = self.variables.iter().position(|group| group.contains(&i)) {
+                write!(f, "{}", (b'g' + v as u8) as char)?;
+            } else {
+                write!(f, "?")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Any,
+    Fixed(u8),
+    Class(u16),
+    Variable(char),
+}
+
+// the inside of [...]: hex digits and ranges, negated by a leading ^
+fn parse_class(class: &str) -> Result<u16, String> {
+    let (negate, items) = match class.strip_prefix('^') {
+        Some(items) => (true, items),
+        None => (false, class),
+    };
+    let digit = |c: char| {
+        c.to_digit(16)
+            .ok_or_else(|| format!("'{}' in [{}] is not a hex digit", c, class))
+    };
+    let mut set = 0u16;
+    let chars: Vec<char> = items.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let low = digit(chars[i])?;
+        let high = if chars.get(i + 1) == Some(&'-') && i + 2 < chars.len() {
+            i += 2;
+            digit(chars[i])?
+        } else {
+            low
+        };
+        if high < low {
+            return Err(format!("range in [{}] runs backwards", class));
+        }
+        for n in low..=high {
+            set |= 1 << n;
+        }
+        i += 1;
+    }
+    if negate {
+        set = !set;
+    }
+    if set == 0 {
+        return Err(format!("[{}] accepts no character", class));
+    }
+    Ok(set)
+}
+
+fn nibble(address: &[u8; 20], position: usize) -> u8 {
+    let byte = address[position / 2];
+    if position.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0x0F
+    }
+}
+
+impl Matcher for Pattern {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.fixed.matches(address)
+            && self
+                .classes
+                .iter()
+                .all(|&(i, set)| set >> nibble(address, i) & 1 == 1)
+            && self.variables.iter().all(|group| {
+                let first = nibble(address, group[0]);
+                group[1..].iter().all(|&i| nibble(address, i) == first)
+            })
+    }
+
+    fn probability(&self) -> Option<f64> {
+        let classes: f64 = self
+            .classes
+            .iter()
+            .map(|&(_, set)| set.count_ones() as f64 / 16.0)
+            .product();
+        let variables: f64 = self
+            .variables
+            .iter()
+            .map(|group| 16f64.powi(1 - group.len() as i32))
+            .product();
+        Some(self.fixed.probability()? * classes * variables)
+    }
+}
@@ -0,0 +1,311 @@
+use crate::matcher::{Matcher, NibbleMask};
+use std::fmt;
+use std::str::FromStr;
+
+// deadbeef, baddcafe and 1337beef as nibbles, the words the Python
+// generator's hexspeak pattern looks for
+const HEXSPEAK: [[u8; 8]; 3] = [
+    [0xd, 0xe, 0xa, 0xd, 0xb, 0xe, 0xe, 0xf],
+    [0xb, 0xa, 0xd, 0xd, 0xc, 0xa, 0xf, 0xe],
+    [0x1, 0x3, 0x3, 0x7, 0xb, 0xe, 0xe, 0xf],
+];
+
+/// The Python generator's named patterns as native matchers, written
+/// `name` or `name:N`. Every preset knows the chance that a random address
+/// matches it, so difficulty can be reported up front.
+///
+/// ```
+/// use hash_hunter::preset::Preset;
+///
+/// let preset: Preset = "ascending:6".parse().unwrap();
+/// assert_eq!(preset, Preset::Ascending(6));
+/// assert!(preset.probability() < 1e-4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// `0000` right after the first four characters.
+    FourZeros,
+    /// A run of N decimal digits anywhere (`triple-digits` is 3).
+    Digits(usize),
+    /// N characters each one above the last, like `3456` or `9abc`.
+    Ascending(usize),
+    /// N characters each one below the last, like `6543`.
+    Descending(usize),
+    /// The first N characters mirror the last N.
+    Palindrome(usize),
+    /// The first N characters read the same backwards, the Python
+    /// generator's check (on the first 12, compared case-insensitively).
+    LeadingPalindrome(usize),
+    /// The same byte N times in a row.
+    RepeatedByte(usize),
+    /// A run of N characters that are all `0` or `1`.
+    Binary(usize),
+    /// `deadbeef`, `baddcafe` or `1337beef` anywhere.
+    Hexspeak,
+}
+
+impl Preset {
+    /// Names accepted by [`Preset::from_str`].
+    pub const NAMES: [&'static str; 10] = [
+        "four-zeros",
+        "triple-digits",
+        "digits",
+        "ascending",
+        "descending",
+        "palindrome",
+        "leading-palindrome",
+        "repeated-byte",
+        "binary",
+        "hexspeak",
+    ];
+
+    /// Chance that a uniformly random address matches.
+    pub fn probability(&self) -> f64 {
+        match *self {
+            Preset::FourZeros => 16f64.powi(-4),
+            Preset::Digits(n) => run_probability(16, 40, n, |v| v < 10, |_, w| w < 10),
+            Preset::Ascending(n) => run_probability(16, 40, n, |_| true, |v, w| w == v + 1),
+            Preset::Descending(n) => run_probability(16, 40, n, |_| true, |v, w| w + 1 == v),
+            Preset::Palindrome(n) => 16f64.powi(-(n as i32)),
+            // the second half is fixed by the first, the middle one is free
+            Preset::LeadingPalindrome(n) => 16f64.powi(-(n as i32 / 2)),
+            Preset::RepeatedByte(n) => run_probability(256, 20, n, |_| true, |v, w| w == v),
+            Preset::Binary(n) => run_probability(16, 40, n, |v| v < 2, |_, w| w < 2),
+            Preset::Hexspeak => {
+                // 33 offsets per word; overlapping hits are rare enough to
+                // treat the offsets as independent
+                let miss = 1.0 - 16f64.powi(-8);
+                1.0 - miss.powi(33 * HEXSPEAK.len() as i32)
+            }
+        }
+    }
+}
+
+impl Preset {
+    /// Whether some address agreeing with the characters `mask` pins
+    /// matches.
+    pub fn possible(&self, mask: &NibbleMask) -> bool {
+        let nibble = |i: usize, v: u8| mask.nibble(i).is_none_or(|n| n == v);
+        let byte = |i: usize, v: u8| nibble(2 * i, v >> 4) && nibble(2 * i + 1, v & 0x0F);
+        match *self {
+            Preset::FourZeros => (4..8).all(|i| nibble(i, 0)),
+            Preset::Digits(len) => run_possible(16, 40, len, nibble, |v| v < 10, |_, w| w < 10),
+            Preset::Ascending(len) => {
+                run_possible(16, 40, len, nibble, |_| true, |v, w| w == v + 1)
+            }
+            Preset::Descending(len) => {
+                run_possible(16, 40, len, nibble, |_| true, |v, w| w + 1 == v)
+            }
+            Preset::Palindrome(len) => (0..len).all(|i| mirrors(mask, i, 39 - i)),
+            Preset::LeadingPalindrome(len) => (0..len / 2).all(|i| mirrors(mask, i, len - 1 - i)),
+            Preset::RepeatedByte(len) => run_possible(256, 20, len, byte, |_| true, |v, w| w == v),
+            Preset::Binary(len) => run_possible(16, 40, len, nibble, |v| v < 2, |_, w| w < 2),
+            Preset::Hexspeak => (0..=32).any(|at| {
+                HEXSPEAK
+                    .iter()
+                    .any(|word| word.iter().enumerate().all(|(i, &v)| nibble(at + i, v)))
+            }),
+        }
+    }
+}
+
+impl Matcher for Preset {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        let n = nibbles(address);
+        match *self {
+            Preset::FourZeros => n[4..8] == [0; 4],
+            Preset::Digits(len) => has_run(&n, len, |v| v < 10, |_, w| w < 10),
+            Preset::Ascending(len) => has_run(&n, len, |_| true, |v, w| w == v + 1),
+            Preset::Descending(len) => has_run(&n, len, |_| true, |v, w| w + 1 == v),
+            Preset::Palindrome(len) => (0..len).all(|i| n[i] == n[39 - i]),
+            Preset::LeadingPalindrome(len) => (0..len / 2).all(|i| n[i] == n[len - 1 - i]),
+            Preset::RepeatedByte(len) => has_run(address, len, |_| true, |v, w| w == v),
+            Preset::Binary(len) => has_run(&n, len, |v| v < 2, |_, w| w < 2),
+            Preset::Hexspeak => n
+                .windows(8)
+                .any(|window| HEXSPEAK.iter().any(|word| window == word)),
+        }
+    }
+
+    fn probability(&self) -> Option<f64> {
+        Some(Preset::probability(self))
+    }
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, n) = match value.split_once(':') {
+            Some((name, n)) => {
+                let n = n
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid length in preset {}", value))?;
+                (name, Some(n))
+            }
+            None => (value, None),
+        };
+        let length = |default: usize, max: usize| {
+            let n = n.unwrap_or(default);
+            if (1..=max).contains(&n) {
+                Ok(n)
+            } else {
+                Err(format!("{} takes a length from 1 to {}", name, max))
+            }
+        };
+        let fixed = |preset: Preset| match n {
+            Some(_) => Err(format!("{} takes no length", name)),
+            None => Ok(preset),
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "four-zeros" => fixed(Preset::FourZeros),
+            "triple-digits" => fixed(Preset::Digits(3)),
+            "digits" => length(3, 40).map(Preset::Digits),
+            // no wrapping past f or 0, so 16 is the longest possible run
+            "ascending" => length(4, 16).map(Preset::Ascending),
+            "descending" => length(4, 16).map(Preset::Descending),
+            "palindrome" => length(6, 20).map(Preset::Palindrome),
+            "leading-palindrome" => length(12, 40).map(Preset::LeadingPalindrome),
+            "repeated-byte" => length(4, 20).map(Preset::RepeatedByte),
+            "binary" => length(8, 40).map(Preset::Binary),
+            "hexspeak" => fixed(Preset::Hexspeak),
+            _ => Err(format!(
+                "unknown preset {}, expected one of: {}",
+                name,
+                Preset::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preset::FourZeros => write!(f, "four-zeros"),
+            Preset::Digits(n) => write!(f, "digits:{}", n),
+            Preset::Ascending(n) => write!(f, "ascending:{}", n),
+            Preset::Descending(n) => write!(f, "descending:{}", n),
+            Preset::Palindrome(n) => write!(f, "palindrome:{}", n),
+            Preset::LeadingPalindrome(n) => write!(f, "leading-palindrome:{}", n),
+            Preset::RepeatedByte(n) => write!(f, "repeated-byte:{}", n),
+            Preset::Binary(n) => write!(f, "binary:{}", n),
+            Preset::Hexspeak => write!(f, "hexspeak"),
+        }
+    }
+}
+
+fn nibbles(address: &[u8; 20]) -> [u8; 40] {
+    let mut out = [0u8; 40];
+    for (i, b) in address.iter().enumerate() {
+        out[2 * i] = b >> 4;
+        out[2 * i + 1] = b & 0x0F;
+    }
+    out
+}
+
+// a run of `len` symbols: the first satisfies `member`, each later one
+// `extends` the one before it
+fn has_run(
+    symbols: &[u8],
+    len: usize,
+    member: impl Fn(u8) -> bool,
+    extends: impl Fn(u8, u8) -> bool,
+) -> bool {
+    let mut run = 0;
+    for (i, &s) in symbols.iter().enumerate() {
+        run = if run > 0 && extends(symbols[i - 1], s) {
+            run + 1
+        } else {
+            member(s) as usize
+        };
+        if run >= len {
+            return true;
+        }
+    }
+    false
+}
+
+// whether characters `i` and `j` can be equal
+fn mirrors(mask: &NibbleMask, i: usize, j: usize) -> bool {
+    match (mask.nibble(i), mask.nibble(j)) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+// whether `length` symbols out of `alphabet`, position i limited to the
+// values `allowed(i, _)` takes, can hold a run in the sense of `has_run`:
+// for each start, the values each position of the run can take given the
+// ones before it
+fn run_possible(
+    alphabet: usize,
+    length: usize,
+    len: usize,
+    allowed: impl Fn(usize, u8) -> bool,
+    member: impl Fn(u8) -> bool,
+    extends: impl Fn(u8, u8) -> bool,
+) -> bool {
+    (0..=length.saturating_sub(len)).any(|start| {
+        let mut reachable: Vec<u8> = (0..alphabet)
+            .map(|v| v as u8)
+            .filter(|&v| allowed(start, v) && member(v))
+            .collect();
+        for i in start + 1..start + len {
+            reachable = (0..alphabet)
+                .map(|w| w as u8)
+                .filter(|&w| allowed(i, w) && reachable.iter().any(|&v| extends(v, w)))
+                .collect();
+        }
+        !reachable.is_empty()
+    })
+}
+
+// exact chance that `length` uniform symbols out of `alphabet` contain a
+// run in the sense of `has_run`, by dynamic programming over (last symbol,
+// current run length)
+fn run_probability(
+    alphabet: usize,
+    length: usize,
+    len: usize,
+    member: impl Fn(usize) -> bool,
+    extends: impl Fn(usize, usize) -> bool,
+) -> f64 {
+    let p = 1.0 / alphabet as f64;
+    // state[v][r]: no run yet, last symbol v, current run r (< len)
+    let mut state = vec![vec![0.0f64; len]; alphabet];
+    let mut done = 0.0;
+
+    for (w, runs) in state.iter_mut().enumerate() {
+        let run = member(w) as usize;
+        if run >= len {
+            done += p;
+        } else {
+            runs[run] += p;
+        }
+    }
+    for _ in 1..length {
+        let mut next = vec![vec![0.0f64; len]; alphabet];
+        for (v, runs) in state.iter().enumerate() {
+            for (r, &mass) in runs.iter().enumerate() {
+                if mass == 0.0 {
+                    continue;
+                }
+                for (w, next_runs) in next.iter_mut().enumerate() {
+                    let run = if r > 0 && extends(v, w) {
+                        r + 1
+                    } else {
+                        member(w) as usize
+                    };
+                    if run >= len {
+                        done += mass * p;
+                    } else {
+                        next_runs[run] += mass * p;
+                    }
+                }
+            }
+        }
+        state = next;
+    }
+    done
+}
@@ -0,0 +1,125 @@
+use crate::contract::create2_address;
+use sha3::{Digest, Keccak256};
+
+/// Signature of `Safe.setup`, the call a `createProxyWithNonce` initializer
+/// usually makes.
+pub const SETUP_SIGNATURE: &str =
+    "setup(address[],uint256,address,bytes,address,address,uint256,address)";
+
+/// Calldata for `Safe.setup` with the given owners, threshold and fallback
+/// handler, and no module call or deployment payment.
+///
+/// Fails when the threshold is 0 or above the number of owners, or an owner
+/// is listed twice, all of which `setup` reverts on.
+pub fn setup_initializer(
+    owners: &[[u8; 20]],
+    threshold: u64,
+    fallback_handler: [u8; 20],
+) -> Result<Vec<u8>, String> {
+    if threshold == 0 || threshold as usize > owners.len() {
+        return Err(format!(
+            "threshold {} needs to be between 1 and the {} owners",
+            threshold,
+            owners.len()
+        ));
+    }
+    for (i, owner) in owners.iter().enumerate() {
+        if owners[..i].contains(owner) {
+            return Err(format!("owner 0x{} is listed twice", hex::encode(owner)));
+        }
+    }
+
+    let word = |bytes: &[u8]| {
+        let mut word = [0u8; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        word
+    };
+    // head: owners (offset), threshold, to, data (offset), fallbackHandler,
+    // paymentToken, payment, paymentReceiver; then the two tails
+    let owners_offset = 8 * 32u64;
+    let data_offset = owners_offset + 32 * (1 + owners.len() as u64);
+    let mut calldata = Keccak256::digest(SETUP_SIGNATURE)[..4].to_vec();
+    calldata.extend(word(&owners_offset.to_be_bytes()));
+    calldata.extend(word(&threshold.to_be_bytes()));
+    calldata.extend([0u8; 32]);
+    calldata.extend(word(&data_offset.to_be_bytes()));
+    calldata.extend(word(&fallback_handler));
+    calldata.extend([0u8; 3 * 32]);
+    calldata.extend(word(&(owners.len() as u64).to_be_bytes()));
+    for owner in owners {
+        calldata.extend(word(owner));
+    }
+    // empty data: just its length
+    calldata.extend([0u8; 32]);
+    Ok(calldata)
+}
+
+/// Salt-nonce mining for a Safe created through the proxy factory's
+/// `createProxyWithNonce(singleton, initializer, saltNonce)`.
+///
+/// The factory CREATE2s `proxyCreationCode ++ uint256(singleton)` with salt
+/// `keccak256(keccak256(initializer) ++ saltNonce)`, so the address depends on
+/// the owners and settings as well as the nonce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Safe {
+    pub factory: [u8; 20],
+    pub singleton: [u8; 20],
+    pub init_code_hash: [u8; 32],
+    pub initializer_hash: [u8; 32],
+}
+
+impl Safe {
+    /// `proxy_creation_code` is what the factory's `proxyCreationCode()`
+    /// returns; it differs between Safe releases.
+    pub fn new(
+        factory: [u8; 20],
+        proxy_creation_code: &[u8],
+        singleton: [u8; 20],
+        initializer: &[u8],
+    ) -> Self {
+        let mut init_code = proxy_creation_code.to_vec();
+        init_code.extend([0u8; 12]);
+        init_code.extend(singleton);
+        Self {
+            factory,
+            singleton,
+            init_code_hash: Keccak256::digest(init_code).into(),
+            initializer_hash: Keccak256::digest(initializer).into(),
+        }
+    }
+
+    /// The CREATE2 salt the factory derives from `salt_nonce`.
+    pub fn salt(&self, salt_nonce: &[u8; 32]) -> [u8; 32] {
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(&self.initializer_hash);
+        preimage[32..].copy_from_slice(salt_nonce);
+        Keccak256::digest(preimage).into()
+    }
+
+    /// The address of the Safe created with `salt_nonce`.
+    pub fn address(&self, salt_nonce: &[u8; 32]) -> [u8; 20] {
+        create2_address(&self.factory, &self.salt(salt_nonce), &self.init_code_hash)
+    }
+}
+
+/// A big-endian uint256 in decimal, the way `saltNonce` is passed to the
+/// factory.
+pub fn uint256_decimal(value: &[u8; 32]) -> String {
+    let mut value = *value;
+    let mut digits = Vec::new();
+    loop {
+        // divide by 10 in place, keeping the remainder
+        let mut remainder = 0u32;
+        for byte in value.iter_mut() {
+            let current = remainder << 8 | *byte as u32;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        if value.iter().all(|&b| b == 0) {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
@@ -0,0 +1,80 @@
+use crate::matcher::zero_nibbles;
+
+/// What a leaderboard hunt ranks addresses by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Scoring {
+    /// `0` characters anywhere in the address.
+    #[default]
+    Zeros,
+    /// `0` characters before the first non-zero one.
+    LeadingZeros,
+    /// Whole zero bytes anywhere in the address.
+    ZeroBytes,
+    /// Zero bytes before the first non-zero one.
+    LeadingZeroBytes,
+    /// [`rarity_score`]; lower is rarer.
+    Rarity,
+}
+
+impl Scoring {
+    pub fn score(&self, address: &[u8; 20]) -> f64 {
+        match self {
+            Scoring::Zeros => zero_nibbles(address) as f64,
+            Scoring::LeadingZeros => leading_zeros(address) as f64,
+            Scoring::ZeroBytes => zero_bytes(address) as f64,
+            Scoring::LeadingZeroBytes => leading_zero_bytes(address) as f64,
+            Scoring::Rarity => rarity_score(address),
+        }
+    }
+
+    /// Whether a lower score is the better one.
+    pub fn lower_is_better(&self) -> bool {
+        *self == Scoring::Rarity
+    }
+}
+
+/// Number of leading `0` characters.
+pub fn leading_zeros(address: &[u8; 20]) -> usize {
+    match address.iter().position(|&b| b != 0) {
+        Some(i) => 2 * i + (address[i] >> 4 == 0) as usize,
+        None => 40,
+    }
+}
+
+/// Number of `0x00` bytes.
+pub fn zero_bytes(address: &[u8; 20]) -> usize {
+    address.iter().filter(|&&b| b == 0).count()
+}
+
+/// Number of leading `0x00` bytes.
+pub fn leading_zero_bytes(address: &[u8; 20]) -> usize {
+    address.iter().take_while(|&&b| b == 0).count()
+}
+
+/// Calldata gas for the address as 20 bytes: 4 per zero byte, 16 per other.
+pub fn calldata_gas(address: &[u8; 20]) -> u64 {
+    let zeros = zero_bytes(address) as u64;
+    4 * zeros + 16 * (20 - zeros)
+}
+
+/// Calldata gas saved each time the address is passed, versus the expected
+/// cost of a random address (which has 20/256 zero bytes on average).
+pub fn calldata_gas_saving(address: &[u8; 20]) -> f64 {
+    let random = 20.0 * 16.0 - 12.0 * 20.0 / 256.0;
+    random - calldata_gas(address) as f64
+}
+
+/// The Python generator's rarity score over the lowercase hex: distinct
+/// characters / 16, scaled up by how often the most common one repeats.
+/// Lower is rarer; few distinct characters score lowest, while repeating one
+/// character raises the score rather than lowering it.
+pub fn rarity_score(address: &[u8; 20]) -> f64 {
+    let mut counts = [0usize; 16];
+    for b in address {
+        counts[(b >> 4) as usize] += 1;
+        counts[(b & 0x0F) as usize] += 1;
+    }
+    let unique = counts.iter().filter(|&&c| c > 0).count();
+    let max_count = counts.iter().max().copied().unwrap_or(0);
+    (unique as f64 / 16.0) * (1.0 + max_count as f64 / 40.0)
+}
@@ -0,0 +1,819 @@
+use crate::account::SmartAccount;
+use crate::checksum::to_checksum_address;
+use crate::contract::{Create, Create2, Create3, MAX_SALT_PREFIX, SaltCounter};
+use crate::createx::CreateX;
+use crate::difficulty::Difficulty;
+use crate::keys::{address_from_public_key, generate_keypair, verify_address};
+use crate::leaderboard::Leaderboard;
+use crate::matcher::{
+    All, Case, ChecksumCase, Matcher, MinZeroBytes, NibbleMask, RegexMatcher, binomial_tail,
+    zero_nibbles,
+};
+use crate::pattern::Pattern;
+use crate::safe::Safe;
+use crate::score::Scoring;
+use crate::targets::TargetSet;
+use crate::walk::KeyWalker;
+use rand::rngs::OsRng;
+use regex::Regex;
+use secp256k1::{Secp256k1, SecretKey};
+use sha3::{Digest, Keccak256};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A match found by a [`Search`].
+#[derive(Debug, Clone)]
+pub struct VanityResult {
+    /// `0x`-prefixed address, checksummed if the search asked for it
+    pub address: String,
+    /// what produces `address`
+    pub secret: Secret,
+    /// the EOA whose key is `secret`, for CREATE searches where `address` is
+    /// the contract it deploys; cased like `address`
+    pub deployer: Option<String>,
+    /// label of the [`TargetSet`] entry the address satisfied
+    pub matched: Option<String>,
+    /// attempts made across all threads when the match was found
+    pub attempts: u64,
+}
+
+impl VanityResult {
+    /// Hex private key, for key searches.
+    pub fn priv_key(&self) -> Option<&str> {
+        match &self.secret {
+            Secret::PrivateKey(key) => Some(key),
+            Secret::Salt(_) => None,
+        }
+    }
+
+    /// Hex salt, for contract searches.
+    pub fn salt(&self) -> Option<&str> {
+        match &self.secret {
+            Secret::Salt(salt) => Some(salt),
+            Secret::PrivateKey(_) => None,
+        }
+    }
+}
+
+/// The value a result's address is derived from, hex-encoded without `0x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Secret {
+    PrivateKey(String),
+    Salt(String),
+}
+
+/// What a search generates candidates from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Target {
+    /// Externally owned accounts, from private keys.
+    #[default]
+    Eoa,
+    /// CREATE2 deployments, from salts.
+    Create2(Create2),
+    /// CREATE3 deployments through a factory's proxy, from salts.
+    Create3(Create3),
+    /// CREATE deployments from a fresh EOA, from private keys.
+    Create(Create),
+    /// Safe proxies from the proxy factory, from salt nonces.
+    Safe(Safe),
+    /// CREATE2 or CREATE3 deployments through CreateX, from salts in its
+    /// protected format.
+    CreateX(CreateX),
+    /// ERC-4337 accounts from a proxy-deploying factory, from salts.
+    SmartAccount(SmartAccount),
+}
+
+/// How workers produce candidate keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Strategy {
+    /// An independent random key and full scalar multiplication per attempt.
+    Random,
+    /// Walk k+1, k+2, ... from a random k with batched point additions.
+    Incremental,
+    /// Walk like `Incremental`, and also test -P, λP, λ²P and their
+    /// negations: six addresses per point addition.
+    #[default]
+    Endomorphism,
+}
+
+/// Why a [`Search`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Enough results were found, or every retiring target was.
+    Found = 1,
+    MaxTries,
+    Timeout,
+    /// The stop file appeared.
+    StopFile,
+    /// [`Progress::stop`] was called.
+    Stopped,
+}
+
+/// Live counters shared between a running [`Search`] and whoever watches it.
+#[derive(Debug, Default)]
+pub struct Progress {
+    attempts: AtomicU64,
+    results: AtomicUsize,
+    // StopReason as u8, 0 while running
+    stop: AtomicU8,
+    finished: AtomicBool,
+}
+
+impl Progress {
+    /// Attempts flushed by the workers so far.
+    pub fn attempts(&self) -> u64 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    /// Results found so far.
+    pub fn results(&self) -> usize {
+        self.results.load(Ordering::Relaxed)
+    }
+
+    /// Asks the workers to wind down; results found so far are still
+    /// returned.
+    pub fn stop(&self) {
+        self.stop_with(StopReason::Stopped);
+    }
+
+    /// Why the search stopped, once it has been told to.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        match self.stop.load(Ordering::Relaxed) {
+            1 => Some(StopReason::Found),
+            2 => Some(StopReason::MaxTries),
+            3 => Some(StopReason::Timeout),
+            4 => Some(StopReason::StopFile),
+            5 => Some(StopReason::Stopped),
+            _ => None,
+        }
+    }
+
+    /// Whether every worker has stopped.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    fn is_stopping(&self) -> bool {
+        self.stop.load(Ordering::Relaxed) != 0
+    }
+
+    // back to a fresh run's state
+    fn reset(&self) {
+        self.attempts.store(0, Ordering::Relaxed);
+        self.results.store(0, Ordering::Relaxed);
+        self.stop.store(0, Ordering::Relaxed);
+        self.finished.store(false, Ordering::Relaxed);
+    }
+
+    // the first reason given wins
+    fn stop_with(&self, reason: StopReason) {
+        let _ = self
+            .stop
+            .compare_exchange(0, reason as u8, Ordering::Relaxed, Ordering::Relaxed);
+    }
+}
+
+impl Target {
+    /// Re-derives `result`'s address from its secret and compares, ignoring
+    /// casing.
+    pub fn verify(&self, result: &VanityResult) -> bool {
+        match (self, &result.secret) {
+            (Target::Eoa, Secret::PrivateKey(key)) => verify_address(&result.address, key),
+            (Target::Create(create), Secret::PrivateKey(key)) => {
+                let Some(deployer) = &result.deployer else {
+                    return false;
+                };
+                let Ok(eoa) = parse_address(deployer) else {
+                    return false;
+                };
+                verify_address(deployer, key)
+                    && result.address.trim_start_matches("0x").to_lowercase()
+                        == hex::encode(create.address(&eoa))
+            }
+            (_, Secret::Salt(salt)) => {
+                let Ok(salt) = hex::decode(salt) else {
+                    return false;
+                };
+                let Ok(salt) = salt.try_into() else {
+                    return false;
+                };
+                self.salt_address(&salt).is_some_and(|address| {
+                    result.address.trim_start_matches("0x").to_lowercase() == hex::encode(address)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    // the address a salt deploys to, for salt-mining targets
+    fn salt_address(&self, salt: &[u8; 32]) -> Option<[u8; 20]> {
+        match self {
+            Target::Create2(create2) => Some(create2.address(salt)),
+            Target::Create3(create3) => Some(create3.address(salt)),
+            Target::Safe(safe) => Some(safe.address(salt)),
+            Target::CreateX(createx) => Some(createx.address(salt)),
+            Target::SmartAccount(account) => Some(account.address(salt)),
+            Target::Eoa | Target::Create(_) => None,
+        }
+    }
+}
+
+fn parse_address(address: &str) -> Result<[u8; 20], ()> {
+    let bytes = hex::decode(address.trim_start_matches("0x")).map_err(|_| ())?;
+    bytes.try_into().map_err(|_| ())
+}
+
+type OnResult = dyn Fn(&VanityResult) + Send + Sync;
+
+/// Builder for a multi-threaded vanity address search.
+///
+/// ```
+/// use hash_hunter::{Search, verify_address};
+///
+/// let results = Search::new().prefix("de").suffix("ef").threads(8).run();
+/// for result in &results {
+///     assert!(verify_address(&result.address, result.priv_key().unwrap()));
+/// }
+/// ```
+pub struct Search {
+    target: Target,
+    // anchored hex, checked before anything in `matcher`
+    mask: NibbleMask,
+    // EIP-55 casing of the letters in the prefix and suffix
+    casing: ChecksumCase,
+    case: Case,
+    // kept apart from `matcher` so its odds can account for zeros in `mask`
+    min_zeros: usize,
+    regex: Option<Regex>,
+    matcher: All,
+    targets: Option<Arc<TargetSet>>,
+    leaderboard: Option<Arc<Leaderboard>>,
+    strategy: Strategy,
+    checksum: bool,
+    step: u64,
+    max_tries: u64,
+    max_results: Option<usize>,
+    timeout: Option<Duration>,
+    stop_file: Option<PathBuf>,
+    on_result: Option<Box<OnResult>>,
+    threads: usize,
+    progress: Arc<Progress>,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Search {
+    /// A search that accepts any address, on every core, until `u64::MAX` tries.
+    pub fn new() -> Self {
+        Self {
+            target: Target::default(),
+            mask: NibbleMask::default(),
+            casing: ChecksumCase::default(),
+            case: Case::default(),
+            min_zeros: 0,
+            regex: None,
+            matcher: All::default(),
+            targets: None,
+            leaderboard: None,
+            strategy: Strategy::default(),
+            checksum: false,
+            step: 50_000,
+            max_tries: u64::MAX,
+            max_results: None,
+            timeout: None,
+            stop_file: None,
+            on_result: None,
+            threads: num_cpus::get(),
+            progress: Arc::new(Progress::default()),
+        }
+    }
+
+    /// What to generate candidates from; key search by default.
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Adds a criterion; a candidate must satisfy every one added.
+    pub fn matcher(mut self, matcher: impl Matcher + 'static) -> Self {
+        self.matcher.0.push(Box::new(matcher));
+        self
+    }
+
+    /// Requires the fixed nibbles of `mask`, merged with any prefix or suffix
+    /// already set.
+    ///
+    /// # Panics
+    ///
+    /// If `mask` pins a nibble already pinned to a different value.
+    pub fn mask(mut self, mask: NibbleMask) -> Self {
+        self.mask = self
+            .mask
+            .merge(&mask)
+            .expect("patterns disagree where they overlap");
+        self
+    }
+
+    /// Requires a positional [`Pattern`]: its pinned characters join the
+    /// anchored mask, the rest is checked after it.
+    ///
+    /// # Panics
+    ///
+    /// If the pattern pins a nibble already pinned to a different value.
+    pub fn pattern(self, pattern: &Pattern) -> Self {
+        self.mask(*pattern.fixed()).matcher(pattern.unpinned())
+    }
+
+    /// Hex the address must start with (no `0x`). Letter casing only counts
+    /// under [`Case::Exact`].
+    pub fn prefix(mut self, pattern: &str) -> Self {
+        self.casing.extend(&ChecksumCase::prefix(pattern));
+        self.mask(NibbleMask::prefix(pattern))
+    }
+
+    /// Hex the address must end with.
+    pub fn suffix(mut self, pattern: &str) -> Self {
+        self.casing.extend(&ChecksumCase::suffix(pattern));
+        self.mask(NibbleMask::suffix(pattern))
+    }
+
+    /// Whether letters in the prefix, suffix and regex must match the
+    /// EIP-55 casing.
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Cased letters the search enforces; each doubles the expected attempts.
+    pub fn cased_letters(&self) -> &ChecksumCase {
+        static NONE: ChecksumCase = ChecksumCase::new();
+        match self.case {
+            Case::Insensitive => &NONE,
+            Case::Exact => &self.casing,
+        }
+    }
+
+    /// Hunts every target in `targets` at once, on top of the other criteria.
+    /// Unless [`Search::max_results`] says otherwise, stops at the first hit,
+    /// or once every target has been found if the set retires them.
+    pub fn targets(mut self, targets: TargetSet) -> Self {
+        self.targets = Some(Arc::new(targets));
+        self
+    }
+
+    /// Ranks every candidate meeting the criteria by `scoring`, keeping the
+    /// best `size` in [`Search::leaderboard`] instead of stopping on a hit.
+    /// The search then runs until `max_tries`, a timeout or a stop.
+    pub fn top(mut self, size: usize, scoring: Scoring) -> Self {
+        self.leaderboard = Some(Arc::new(Leaderboard::new(size, scoring)));
+        self
+    }
+
+    /// Handle on the board set up by [`Search::top`], readable while the
+    /// search runs.
+    pub fn leaderboard(&self) -> Option<Arc<Leaderboard>> {
+        self.leaderboard.clone()
+    }
+
+    /// Chance that one attempt meets every criterion, or `None` when a regex,
+    /// target set or custom matcher makes it unknown. Zeros the mask pins
+    /// count toward `min_zeros`; other criteria are taken as independent.
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        if self.regex.is_some() || self.targets.is_some() {
+            return None;
+        }
+        let pinned: Vec<u8> = (0..40).filter_map(|i| self.mask.nibble(i)).collect();
+        let pinned_zeros = pinned.iter().filter(|&&n| n == 0).count();
+        let zeros = binomial_tail(
+            40 - pinned.len(),
+            1.0 / 16.0,
+            self.min_zeros.saturating_sub(pinned_zeros),
+        );
+        let mut probability = 16f64.powi(-(pinned.len() as i32))
+            * zeros
+            * self.cased_letters().probability()?
+            * self.matcher.probability()?;
+        if let Target::Create(create) = &self.target
+            && create.match_deployer
+        {
+            // deployer and contract are independent hashes
+            probability *= probability;
+        }
+        Some(Difficulty::new(probability))
+    }
+
+    /// Minimum number of `0` characters anywhere in the address.
+    pub fn min_zeros(mut self, min_zeros: usize) -> Self {
+        self.min_zeros = self.min_zeros.max(min_zeros);
+        self
+    }
+
+    /// Minimum number of leading `0x00` bytes, merged into the anchored mask.
+    ///
+    /// # Panics
+    ///
+    /// If more than 20, or a prefix already set has a non-zero there.
+    pub fn leading_zero_bytes(self, bytes: usize) -> Self {
+        self.mask(NibbleMask::prefix(&"00".repeat(bytes)))
+    }
+
+    /// Minimum number of `0x00` bytes anywhere in the address.
+    pub fn min_zero_bytes(self, bytes: usize) -> Self {
+        self.matcher(MinZeroBytes(bytes))
+    }
+
+    /// Regex the address hex must match, in the casing [`Search::case`] picks.
+    pub fn regex(mut self, regex: Regex) -> Self {
+        self.regex = Some(regex);
+        self
+    }
+
+    /// How candidate keys are generated for [`Target::Eoa`] and
+    /// [`Target::Create`].
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Report addresses in their EIP-55 casing (always on under
+    /// [`Case::Exact`]).
+    pub fn checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Attempts each worker batches up before publishing them to [`Progress`].
+    pub fn step(mut self, step: u64) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    /// Give up after roughly this many attempts.
+    pub fn max_tries(mut self, max_tries: u64) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// Stop after this many results; `usize::MAX` keeps hunting until another
+    /// limit is hit. One by default.
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = Some(max_results.max(1));
+        self
+    }
+
+    /// Give up after roughly this long.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stop once a file appears at `path`, looked for a few times a second.
+    pub fn stop_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.stop_file = Some(path.into());
+        self
+    }
+
+    /// Called with each result as it is found, from the worker that found
+    /// it, one result at a time.
+    pub fn on_result(mut self, on_result: impl Fn(&VanityResult) + Send + Sync + 'static) -> Self {
+        self.on_result = Some(Box::new(on_result));
+        self
+    }
+
+    /// Number of worker threads.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Handle for watching the search from another thread.
+    pub fn progress(&self) -> Arc<Progress> {
+        Arc::clone(&self.progress)
+    }
+
+    /// Runs the search on a dedicated pool, blocking until enough matches are
+    /// found or a limit is hit; [`Progress::stop_reason`] says which. Each
+    /// run starts its [`Progress`] over.
+    pub fn run(&self) -> Vec<VanityResult> {
+        self.progress.reset();
+        // a timeout too far out to represent never fires
+        let deadline = self
+            .timeout
+            .and_then(|timeout| Instant::now().checked_add(timeout));
+        let results = self.run_on(&self.progress, deadline, false);
+        self.progress.finished.store(true, Ordering::Relaxed);
+        results
+    }
+
+    /// Attempts per second this search makes, measured by running it for
+    /// `duration` with hits counted but neither kept nor ranked, so matching
+    /// and any extra hashing cost what they will in the real run. The
+    /// search's own progress is left untouched.
+    pub fn measure_rate(&self, duration: Duration) -> f64 {
+        let progress = Progress::default();
+        let start = Instant::now();
+        self.run_on(&progress, start.checked_add(duration), true);
+        progress.attempts() as f64 / start.elapsed().as_secs_f64()
+    }
+
+    fn run_on(
+        &self,
+        progress: &Progress,
+        deadline: Option<Instant>,
+        dry_run: bool,
+    ) -> Vec<VanityResult> {
+        let results = Mutex::new(Vec::new());
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.threads)
+            .build()
+            .expect("Failed to build worker pool");
+
+        pool.scope(|s| {
+            for _ in 0..self.threads {
+                s.spawn(|_| self.worker(&results, progress, deadline, dry_run));
+            }
+        });
+
+        results.into_inner().unwrap()
+    }
+
+    // results to collect before stopping
+    fn result_limit(&self) -> usize {
+        match (self.max_results, &self.targets) {
+            (Some(max_results), _) => max_results,
+            (None, Some(targets)) if targets.retires() => usize::MAX,
+            (None, _) => 1,
+        }
+    }
+
+    fn worker(
+        &self,
+        results: &Mutex<Vec<VanityResult>>,
+        progress: &Progress,
+        deadline: Option<Instant>,
+        dry_run: bool,
+    ) {
+        let mut worker = Worker {
+            search: self,
+            progress,
+            dry_run,
+            regex: self
+                .regex
+                .clone()
+                .map(|regex| RegexMatcher::new(regex, self.case)),
+            results,
+            deadline,
+            stop_file_checked: Instant::now(),
+            local_attempts: 0,
+        };
+        let mut rng = OsRng;
+
+        match (&self.target, self.strategy) {
+            (Target::Create2(create2), _) => {
+                worker.mine_salts(&create2.salt_prefix, |salt| create2.address(salt));
+            }
+            (Target::Create3(create3), _) => {
+                worker.mine_salts(&create3.salt_prefix, |salt| create3.address(salt));
+            }
+            (Target::CreateX(createx), _) => {
+                worker.mine_salts(&createx.salt_prefix(), |salt| createx.address(salt));
+            }
+            (Target::Safe(safe), _) => {
+                // nonces below 2^64, short enough to type into a wallet
+                worker.mine_salts(&[0; MAX_SALT_PREFIX], |nonce| safe.address(nonce));
+            }
+            (Target::SmartAccount(account), _) => {
+                // uint256 salts, kept below 2^64 like Safe nonces
+                worker.mine_salts(&[0; MAX_SALT_PREFIX], |salt| account.address(salt));
+            }
+            (Target::Eoa | Target::Create(_), Strategy::Random) => {
+                let secp = Secp256k1::signing_only();
+                while worker.running() {
+                    let (secret_key, public_key) = generate_keypair(&secp, &mut rng);
+                    worker.check_key(&address_from_public_key(&public_key), || {
+                        private_key(&secret_key)
+                    });
+                }
+            }
+            (Target::Eoa | Target::Create(_), _) => {
+                let mut walker = if self.strategy == Strategy::Endomorphism {
+                    KeyWalker::with_endomorphism(&mut rng)
+                } else {
+                    KeyWalker::new(&mut rng)
+                };
+                while worker.running() {
+                    walker.advance(&mut rng);
+                    for (i, public_key) in walker.public_keys().iter().enumerate() {
+                        let hash = Keccak256::digest(public_key);
+                        worker.check_key(hash[12..].try_into().unwrap(), || {
+                            private_key(&walker.secret_key(i))
+                        });
+                    }
+                }
+            }
+        }
+
+        // add remaining attempts
+        worker.flush();
+    }
+}
+
+// salts tried between checks of the stop conditions
+const SALT_BATCH: usize = 1024;
+
+// attempts between looks at the clock, however large the step
+const CLOCK_INTERVAL: u64 = 256;
+
+// time between looks for the stop file
+const STOP_FILE_INTERVAL: Duration = Duration::from_millis(100);
+
+fn private_key(secret_key: &SecretKey) -> Secret {
+    Secret::PrivateKey(hex::encode(secret_key.secret_bytes()))
+}
+
+// per-thread bookkeeping shared by every target and key strategy
+struct Worker<'a> {
+    search: &'a Search,
+    progress: &'a Progress,
+    // count hits as misses, for measuring the rate
+    dry_run: bool,
+    regex: Option<RegexMatcher>,
+    results: &'a Mutex<Vec<VanityResult>>,
+    deadline: Option<Instant>,
+    stop_file_checked: Instant,
+    local_attempts: u64,
+}
+
+impl Worker<'_> {
+    fn running(&self) -> bool {
+        !self.progress.is_stopping()
+    }
+
+    fn mine_salts(&mut self, prefix: &[u8], address: impl Fn(&[u8; 32]) -> [u8; 20]) {
+        let mut salts = SaltCounter::new(prefix, &mut OsRng);
+        while self.running() {
+            for _ in 0..SALT_BATCH {
+                let salt = salts.next_salt();
+                self.check(&address(&salt), None, || Secret::Salt(hex::encode(salt)));
+            }
+        }
+    }
+
+    // a key's EOA, or under CREATE the contract that EOA deploys
+    fn check_key(&mut self, eoa: &[u8; 20], secret: impl FnOnce() -> Secret) {
+        match &self.search.target {
+            Target::Create(create) => {
+                // the deployer test needs no extra hash, so it goes first
+                if create.match_deployer && !self.is_match(eoa) {
+                    self.count();
+                    return;
+                }
+                self.check(&create.address(eoa), Some(eoa), secret);
+            }
+            _ => self.check(eoa, None, secret),
+        }
+    }
+
+    fn check(
+        &mut self,
+        address: &[u8; 20],
+        deployer: Option<&[u8; 20]>,
+        secret: impl FnOnce() -> Secret,
+    ) {
+        if self.is_match(address) {
+            if self.dry_run {
+                return self.count();
+            }
+            if let Some(leaderboard) = &self.search.leaderboard {
+                // ranked, never a result that stops the search
+                let score = leaderboard.scoring().score(address);
+                leaderboard.offer(score, || self.result(address, deployer, None, secret));
+                return self.count();
+            }
+
+            let matched = match &self.search.targets {
+                Some(targets) => match targets.claim(address) {
+                    Some(label) => Some(label.to_string()),
+                    // lost the target to another thread
+                    None => return self.count(),
+                },
+                None => None,
+            };
+
+            let result = self.result(address, deployer, matched, secret);
+
+            // the lock also keeps on_result calls one at a time
+            let mut results = self.results.lock().unwrap();
+            let limit = self.search.result_limit();
+            if results.len() < limit {
+                if let Some(on_result) = &self.search.on_result {
+                    on_result(&result);
+                }
+                results.push(result);
+                self.progress
+                    .results
+                    .store(results.len(), Ordering::Relaxed);
+
+                let exhausted = self
+                    .search
+                    .targets
+                    .as_ref()
+                    .is_some_and(|targets| targets.retires() && targets.remaining() == 0);
+                if results.len() >= limit || exhausted {
+                    // signal other threads to stop
+                    self.progress.stop_with(StopReason::Found);
+                }
+            }
+        }
+
+        self.count();
+    }
+
+    fn count(&mut self) {
+        self.local_attempts += 1;
+        if self.local_attempts >= self.search.step {
+            self.flush();
+        } else if self.local_attempts.is_multiple_of(CLOCK_INTERVAL) {
+            self.check_clock();
+        }
+    }
+
+    fn result(
+        &self,
+        address: &[u8; 20],
+        deployer: Option<&[u8; 20]>,
+        matched: Option<String>,
+        secret: impl FnOnce() -> Secret,
+    ) -> VanityResult {
+        VanityResult {
+            address: self.format_address(address),
+            secret: secret(),
+            deployer: deployer.map(|eoa| self.format_address(eoa)),
+            matched,
+            attempts: self.progress.attempts() + self.local_attempts + 1,
+        }
+    }
+
+    // only hits pay for hex encoding and checksumming
+    fn format_address(&self, address: &[u8; 20]) -> String {
+        let address = hex::encode(address);
+        if self.search.checksum || self.search.case == Case::Exact {
+            format!("0x{}", to_checksum_address(&address))
+        } else {
+            format!("0x{}", address)
+        }
+    }
+
+    // cheapest checks first: the nibble mask rejects almost everything
+    fn is_match(&self, address: &[u8; 20]) -> bool {
+        self.search.mask.matches(address)
+            && zero_nibbles(address) >= self.search.min_zeros
+            && (self.search.case == Case::Insensitive || self.search.casing.matches(address))
+            && self.regex.as_ref().is_none_or(|re| re.matches(address))
+            && self.search.matcher.matches(address)
+            && self
+                .search
+                .targets
+                .as_ref()
+                .is_none_or(|t| t.matches(address))
+    }
+
+    // publishes attempts and checks the limits too costly to test per attempt
+    fn flush(&mut self) {
+        let attempts = self
+            .progress
+            .attempts
+            .fetch_add(self.local_attempts, Ordering::Relaxed)
+            + self.local_attempts;
+        self.local_attempts = 0;
+
+        if attempts >= self.search.max_tries {
+            self.progress.stop_with(StopReason::MaxTries);
+        } else {
+            self.check_clock();
+        }
+    }
+
+    // the timeout and stop file, on time rather than attempts so a large
+    // step cannot hold them off
+    fn check_clock(&mut self) {
+        let now = Instant::now();
+        if self.deadline.is_some_and(|deadline| now >= deadline) {
+            self.progress.stop_with(StopReason::Timeout);
+        } else if let Some(path) = &self.search.stop_file
+            && now >= self.stop_file_checked + STOP_FILE_INTERVAL
+        {
+            self.stop_file_checked = now;
+            if path.exists() {
+                self.progress.stop_with(StopReason::StopFile);
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::matcher::{Matcher, NibbleMask};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Many patterns hunted at once, each with a label reported on a hit.
+///
+/// Targets anchored at the start of the address sit in a nibble trie walked
+/// from the first nibble, those anchored only at the end in a second trie
+/// walked from the last, so a candidate is only compared against the targets
+/// sharing its leading or trailing nibbles. Targets anchored at neither end
+/// are scanned linearly.
+///
+/// Patterns are case-insensitive, one per line in [`TargetSet::parse`]:
+///
+/// ```text
+/// # comments and blank lines are skipped
+/// dead                                       prefix
+/// *beef                                      suffix
+/// dead*beef                                  both
+/// ????cafe????????????????????????????????   40 nibbles, ? is any
+/// ```
+///
+/// Patterns pushed under the same label are one target: a hit on any of them
+/// retires them all.
+#[derive(Debug, Default)]
+pub struct TargetSet {
+    targets: Vec<Entry>,
+    labels: Vec<Label>,
+    label_ids: HashMap<String, usize>,
+    prefixes: Trie,
+    suffixes: Trie,
+    floating: Vec<usize>,
+    retire: bool,
+    remaining: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    // index into `labels`
+    label: usize,
+    mask: NibbleMask,
+}
+
+#[derive(Debug)]
+struct Label {
+    name: String,
+    retired: AtomicBool,
+}
+
+impl TargetSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads one pattern per line, labelled with the line itself.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut set = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mask = parse_pattern(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+            set.push(line, mask);
+        }
+        Ok(set)
+    }
+
+    /// Adds a target, or another pattern for the target already labelled
+    /// `label`.
+    pub fn push(&mut self, label: &str, mask: NibbleMask) {
+        let id = self.targets.len();
+        let leading = (0..40).take_while(|&i| mask.nibble(i).is_some()).count();
+        let trailing = (0..40)
+            .rev()
+            .take_while(|&i| mask.nibble(i).is_some())
+            .count();
+
+        if leading > 0 {
+            self.prefixes
+                .insert((0..leading).map(|i| mask.nibble(i).unwrap()), id);
+        } else if trailing > 0 {
+            self.suffixes.insert(
+                (40 - trailing..40).rev().map(|i| mask.nibble(i).unwrap()),
+                id,
+            );
+        } else {
+            self.floating.push(id);
+        }
+
+        let label = *self.label_ids.entry(label.to_string()).or_insert_with(|| {
+            self.labels.push(Label {
+                name: label.to_string(),
+                retired: AtomicBool::new(false),
+            });
+            self.remaining.fetch_add(1, Ordering::Relaxed);
+            self.labels.len() - 1
+        });
+        self.targets.push(Entry { label, mask });
+    }
+
+    /// Whether a target stops matching once a hit for it is claimed.
+    pub fn retire(mut self, retire: bool) -> Self {
+        self.retire = retire;
+        self
+    }
+
+    pub fn retires(&self) -> bool {
+        self.retire
+    }
+
+    /// Targets, counting each label once.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Targets not yet retired.
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::Relaxed)
+    }
+
+    /// Label of a live target `address` satisfies, retiring it if the set
+    /// retires targets. `None` if no live target matches, including when
+    /// another thread retired the last one first.
+    pub fn claim(&self, address: &[u8; 20]) -> Option<&str> {
+        self.find(address, |label| {
+            !self.retire
+                || label
+                    .retired
+                    .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+        })
+        .map(|label| {
+            if self.retire {
+                self.remaining.fetch_sub(1, Ordering::Relaxed);
+            }
+            label.name.as_str()
+        })
+    }
+
+    // label of the first live target matching `address` that `accept` takes
+    fn find(&self, address: &[u8; 20], mut accept: impl FnMut(&Label) -> bool) -> Option<&Label> {
+        let nibble = |i: usize| {
+            if i.is_multiple_of(2) {
+                address[i / 2] >> 4
+            } else {
+                address[i / 2] & 0x0F
+            }
+        };
+        let mut candidate = |id: usize| {
+            let entry = &self.targets[id];
+            let label = &self.labels[entry.label];
+            !label.retired.load(Ordering::Relaxed) && entry.mask.matches(address) && accept(label)
+        };
+
+        self.prefixes
+            .walk((0..40).map(nibble))
+            .chain(self.suffixes.walk((0..40).rev().map(nibble)))
+            .chain(self.floating.iter().copied())
+            .find(|&id| candidate(id))
+            .map(|id| &self.labels[self.targets[id].label])
+    }
+}
+
+impl Matcher for TargetSet {
+    fn matches(&self, address: &[u8; 20]) -> bool {
+        self.find(address, |_| true).is_some()
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Result<NibbleMask, String> {
+    let mask = parse_mask(pattern)?;
+    if mask.is_empty() {
+        return Err("pattern fixes no nibbles".to_string());
+    }
+    Ok(mask)
+}
+
+fn parse_mask(pattern: &str) -> Result<NibbleMask, String> {
+    let pattern = pattern.strip_prefix("0x").unwrap_or(pattern);
+    let hex = |part: &str| {
+        if let Some(c) = part.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("'{}' is not a hex character", c));
+        }
+        if part.len() > 40 {
+            return Err("pattern longer than an address".to_string());
+        }
+        Ok(())
+    };
+
+    if let Some((prefix, suffix)) = pattern.split_once('*') {
+        hex(prefix)?;
+        hex(suffix)?;
+        NibbleMask::prefix(prefix)
+            .merge(&NibbleMask::suffix(suffix))
+            .ok_or_else(|| "prefix and suffix disagree where they overlap".to_string())
+    } else if pattern.contains('?') {
+        if pattern.len() != 40 {
+            return Err("masks with ? must be 40 characters".to_string());
+        }
+        let mut mask = NibbleMask::default();
+        for (i, c) in pattern.char_indices().filter(|&(_, c)| c != '?') {
+            hex(&c.to_string())?;
+            mask = mask.merge(&NibbleMask::at(i, &c.to_string())).unwrap();
+        }
+        Ok(mask)
+    } else {
+        hex(pattern)?;
+        Ok(NibbleMask::prefix(pattern))
+    }
+}
+
+// nibble trie over target ids, root at index 0
+#[derive(Debug)]
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    // index into `nodes`, 0 for none (the root is never a child)
+    children: [u32; 16],
+    targets: Vec<usize>,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+}
+
+impl Trie {
+    fn insert(&mut self, path: impl Iterator<Item = u8>, id: usize) {
+        let mut node = 0;
+        for nibble in path {
+            let child = self.nodes[node].children[nibble as usize];
+            node = if child == 0 {
+                self.nodes.push(Node::default());
+                let child = self.nodes.len() - 1;
+                self.nodes[node].children[nibble as usize] = child as u32;
+                child
+            } else {
+                child as usize
+            };
+        }
+        self.nodes[node].targets.push(id);
+    }
+
+    // ids stored on the nodes along `path`, shallowest first
+    fn walk(&self, path: impl Iterator<Item = u8>) -> impl Iterator<Item = usize> {
+        let mut node = Some(0);
+        let mut path = path;
+        std::iter::from_fn(move || {
+            let current = node?;
+            node = path
+                .next()
+                .map(|nibble| self.nodes[current].children[nibble as usize] as usize)
+                .filter(|&child| child != 0);
+            Some(&self.nodes[current].targets)
+        })
+        .flatten()
+        .copied()
+    }
+}
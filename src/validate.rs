@@ -0,0 +1,167 @@
+//! Up-front checks that a hunt's criteria are well formed and can be met,
+//! so a typo fails at once instead of burning CPU until `max_tries`.
+
+use crate::matcher::{BitMask, Case, NibbleMask};
+use crate::preset::Preset;
+use regex::Regex;
+use regex_automata::dfa::{Automaton, dense};
+use regex_automata::util::start;
+use regex_automata::{Anchored, util::primitives::StateID};
+use std::collections::HashSet;
+
+/// The hex of a prefix or suffix without its optional `0x`, checked to fit
+/// in an address.
+pub fn hex_pattern(pattern: &str) -> Result<&str, String> {
+    let hex = pattern.strip_prefix("0x").unwrap_or(pattern);
+    let skipped = pattern.len() - hex.len();
+    if let Some((i, c)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "'{}' at character {} of {} is not a hex digit",
+            c,
+            skipped + i + 1,
+            pattern
+        ));
+    }
+    if hex.len() > 40 {
+        return Err(format!(
+            "{} has {} hex digits, more than the 40 in an address",
+            pattern,
+            hex.len()
+        ));
+    }
+    Ok(hex)
+}
+
+/// First address character (0-based) where hex `a` placed at nibble `a_at`
+/// and hex `b` placed at `b_at` ask for different things. Under
+/// [`Case::Exact`] a letter's casing has to agree too.
+pub fn conflict(a: &str, a_at: usize, b: &str, b_at: usize, case: Case) -> Option<usize> {
+    let start = a_at.max(b_at);
+    let end = (a_at + a.len()).min(b_at + b.len());
+    (start..end).find(|&i| {
+        let (x, y) = (a.as_bytes()[i - a_at], b.as_bytes()[i - b_at]);
+        match case {
+            Case::Insensitive => !x.eq_ignore_ascii_case(&y),
+            Case::Exact => x != y,
+        }
+    })
+}
+
+/// Checks that `min_zeros` zero characters fit around what `mask` pins.
+pub fn min_zeros(mask: &NibbleMask, min_zeros: usize) -> Result<(), String> {
+    let room = (0..40)
+        .filter(|&i| mask.nibble(i).is_none_or(|n| n == 0))
+        .count();
+    if min_zeros > room {
+        return Err(format!(
+            "{} zeros asked for, but the pattern leaves room for at most {}",
+            min_zeros, room
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `min_zero_bytes` zero bytes fit around what `mask` pins.
+pub fn min_zero_bytes(mask: &NibbleMask, min_zero_bytes: usize) -> Result<(), String> {
+    let room = (0..20)
+        .filter(|&i| {
+            [2 * i, 2 * i + 1]
+                .iter()
+                .all(|&n| mask.nibble(n).is_none_or(|n| n == 0))
+        })
+        .count();
+    if min_zero_bytes > room {
+        return Err(format!(
+            "{} zero bytes asked for, but the pattern leaves room for at most {}",
+            min_zero_bytes, room
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `regex` matches at least one address hex in the casing
+/// [`Case`] gives it: lowercase, or any mix of cases for [`Case::Exact`].
+///
+/// Walks a DFA of the regex over every 40-character hex string at once;
+/// regexes the DFA cannot be built for, like Unicode word boundaries, are
+/// let through.
+pub fn regex(regex: &Regex, case: Case) -> Result<(), String> {
+    let Ok(dfa) = dense::DFA::new(regex.as_str()) else {
+        return Ok(());
+    };
+    let Ok(start) = dfa.start_state(&start::Config::new().anchored(Anchored::No)) else {
+        return Ok(());
+    };
+    let alphabet: Vec<u8> = match case {
+        Case::Insensitive => b"0123456789abcdef".to_vec(),
+        Case::Exact => b"0123456789abcdefABCDEF".to_vec(),
+    };
+
+    // every state the DFA can be in after each prefix of the address
+    let mut states: HashSet<StateID> = HashSet::from([start]);
+    for _ in 0..40 {
+        let mut next = HashSet::new();
+        for &state in &states {
+            for &byte in &alphabet {
+                let to = dfa.next_state(state, byte);
+                // matches are reported one byte late, so any later bytes do
+                if dfa.is_match_state(to) || dfa.is_quit_state(to) {
+                    return Ok(());
+                }
+                if !dfa.is_dead_state(to) {
+                    next.insert(to);
+                }
+            }
+        }
+        states = next;
+    }
+    if states
+        .iter()
+        .any(|&state| dfa.is_match_state(dfa.next_eoi_state(state)))
+    {
+        return Ok(());
+    }
+    Err(format!(
+        "{} can never match a 40-character {} address",
+        regex.as_str(),
+        match case {
+            Case::Insensitive => "lowercase hex",
+            Case::Exact => "hex",
+        }
+    ))
+}
+
+/// First address character (0-based) `mask` pins to a value `bits` rules
+/// out.
+pub fn mask_bits(mask: &NibbleMask, bits: &BitMask) -> Option<usize> {
+    (0..40).find(|&i| {
+        mask.nibble(i).is_some_and(|nibble| {
+            let shift = if i.is_multiple_of(2) { 4 } else { 0 };
+            let bit_mask = bits.mask()[i / 2] >> shift & 0x0F;
+            let bit_value = bits.value()[i / 2] >> shift & 0x0F;
+            nibble & bit_mask != bit_value
+        })
+    })
+}
+
+/// Lowest bit (numbered from the least significant, as in
+/// [`BitMask::new`]) that `a` and `b` both fix, to different values.
+pub fn bits(a: &BitMask, b: &BitMask) -> Option<usize> {
+    (0..160).find(|&bit| {
+        let (byte, shift) = (19 - bit / 8, bit % 8);
+        let both = a.mask()[byte] & b.mask()[byte];
+        (both & (a.value()[byte] ^ b.value()[byte])) >> shift & 1 == 1
+    })
+}
+
+/// Checks that `preset` can match an address agreeing with what `mask`
+/// pins.
+pub fn preset(preset: &Preset, mask: &NibbleMask) -> Result<(), String> {
+    if preset.possible(mask) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} can never match with the characters the prefix, suffix, zero bytes or mask pin",
+        preset
+    ))
+}
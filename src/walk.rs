@@ -0,0 +1,221 @@
+use crate::field::{FieldElement, batch_invert};
+use rand::RngCore;
+use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey};
+use std::sync::OnceLock;
+
+/// Points produced per field inversion.
+pub const BATCH_SIZE: usize = 1024;
+
+/// Candidate keys derived from each point when the endomorphism is used:
+/// P, -P, λP, -λP, λ²P and -λ²P.
+pub const ENDOMORPHISM_VARIANTS: usize = 6;
+
+// cube root of unity mod p: λ(x, y) = (βx, y)
+const BETA: [u8; 32] =
+    hex_bytes(b"7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee");
+
+// the matching cube root of unity mod n
+const LAMBDA: [u8; 32] =
+    hex_bytes(b"5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72");
+
+const fn hex_bytes(hex: &[u8; 64]) -> [u8; 32] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            _ => c - b'a' + 10,
+        }
+    }
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (nibble(hex[2 * i]) << 4) | nibble(hex[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AffinePoint {
+    x: FieldElement,
+    y: FieldElement,
+}
+
+impl AffinePoint {
+    fn from_public_key(public_key: &PublicKey) -> Self {
+        let serialized = public_key.serialize_uncompressed();
+        Self {
+            x: FieldElement::from_bytes(&serialized[1..33]),
+            y: FieldElement::from_bytes(&serialized[33..]),
+        }
+    }
+}
+
+// 1G, 2G, ..., BATCH_SIZE*G, computed once and shared by every walker
+fn generator_table() -> &'static [AffinePoint] {
+    static TABLE: OnceLock<Vec<AffinePoint>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let secp = Secp256k1::signing_only();
+        (1..=BATCH_SIZE as u64)
+            .map(|i| {
+                let secret_key = SecretKey::from_slice(&offset_bytes(i)).unwrap();
+                AffinePoint::from_public_key(&PublicKey::from_secret_key(&secp, &secret_key))
+            })
+            .collect()
+    })
+}
+
+fn write_point(out: &mut [u8; 64], x: &FieldElement, y: &FieldElement) {
+    x.write_bytes(&mut out[..32]);
+    y.write_bytes(&mut out[32..]);
+}
+
+fn offset_bytes(offset: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&offset.to_be_bytes());
+    bytes
+}
+
+/// Walks public keys k+1, k+2, ... from a random scalar k, one addition of G
+/// per key instead of a full scalar multiplication.
+///
+/// Points are produced [`BATCH_SIZE`] at a time: each batch adds 1G..NG from
+/// a precomputed table to the current base point, and all of the slope
+/// denominators are inverted together with one field inversion, so every
+/// point comes out already in affine form ready for hashing.
+///
+/// With the endomorphism enabled every point P = (x, y) also yields -P,
+/// λP = (βx, y), λ²P = (β²x, y) and their negations for two field
+/// multiplications and a negation, six candidate keys per addition.
+pub struct KeyWalker {
+    start: SecretKey,
+    // offset of `base` from `start`
+    offset: u64,
+    // offset of the first point in the current batch
+    first: u64,
+    base: AffinePoint,
+    endomorphism: bool,
+    beta: FieldElement,
+    beta_squared: FieldElement,
+    points: Vec<AffinePoint>,
+    denominators: Vec<FieldElement>,
+    scratch: Vec<FieldElement>,
+    serialized: Vec<[u8; 64]>,
+}
+
+impl KeyWalker {
+    /// Starts a walk from a fresh random scalar.
+    pub fn new<R: RngCore>(rng: &mut R) -> Self {
+        Self::build(rng, false)
+    }
+
+    /// Starts a walk that derives [`ENDOMORPHISM_VARIANTS`] keys per point.
+    pub fn with_endomorphism<R: RngCore>(rng: &mut R) -> Self {
+        Self::build(rng, true)
+    }
+
+    fn build<R: RngCore>(rng: &mut R, endomorphism: bool) -> Self {
+        let variants = if endomorphism {
+            ENDOMORPHISM_VARIANTS
+        } else {
+            1
+        };
+        let beta = FieldElement::from_bytes(&BETA);
+        let mut walker = Self {
+            start: SecretKey::new(rng),
+            offset: 0,
+            first: 0,
+            base: AffinePoint::default(),
+            endomorphism,
+            beta,
+            beta_squared: beta.square(),
+            points: Vec::with_capacity(BATCH_SIZE),
+            denominators: Vec::with_capacity(BATCH_SIZE),
+            scratch: Vec::with_capacity(BATCH_SIZE),
+            serialized: vec![[0u8; 64]; BATCH_SIZE * variants],
+        };
+        walker.reseed_from(walker.start);
+        walker
+    }
+
+    fn reseed_from(&mut self, start: SecretKey) {
+        let secp = Secp256k1::signing_only();
+        self.start = start;
+        self.offset = 0;
+        self.base = AffinePoint::from_public_key(&PublicKey::from_secret_key(&secp, &start));
+    }
+
+    /// Advances the walk by one batch.
+    pub fn advance<R: RngCore>(&mut self, rng: &mut R) {
+        let table = generator_table();
+
+        self.denominators.clear();
+        self.denominators
+            .extend(table.iter().map(|g| g.x.sub(&self.base.x)));
+
+        // base == ±iG for some i in the batch; astronomically unlikely, but
+        // there is no affine sum to take, so start over somewhere else
+        if !batch_invert(&mut self.denominators, &mut self.scratch) {
+            self.reseed_from(SecretKey::new(rng));
+            return self.advance(rng);
+        }
+
+        self.points.clear();
+        for (g, inv) in table.iter().zip(&self.denominators) {
+            let lambda = g.y.sub(&self.base.y).mul(inv);
+            let x = lambda.square().sub(&self.base.x).sub(&g.x);
+            let y = lambda.mul(&self.base.x.sub(&x)).sub(&self.base.y);
+            self.points.push(AffinePoint { x, y });
+        }
+
+        // variant-major: P for every point, then -P for every point, ...
+        let (plain, derived) = self.serialized.split_at_mut(BATCH_SIZE);
+        for (i, point) in self.points.iter().enumerate() {
+            write_point(&mut plain[i], &point.x, &point.y);
+
+            if self.endomorphism {
+                let neg_y = point.y.neg();
+                let beta_x = self.beta.mul(&point.x);
+                let beta_squared_x = self.beta_squared.mul(&point.x);
+                write_point(&mut derived[i], &point.x, &neg_y);
+                write_point(&mut derived[BATCH_SIZE + i], &beta_x, &point.y);
+                write_point(&mut derived[2 * BATCH_SIZE + i], &beta_x, &neg_y);
+                write_point(&mut derived[3 * BATCH_SIZE + i], &beta_squared_x, &point.y);
+                write_point(&mut derived[4 * BATCH_SIZE + i], &beta_squared_x, &neg_y);
+            }
+        }
+
+        self.first = self.offset + 1;
+        self.base = self.points[BATCH_SIZE - 1];
+        self.offset += BATCH_SIZE as u64;
+    }
+
+    /// Uncompressed public keys (x ++ y, no `0x04` tag) of the current batch.
+    pub fn public_keys(&self) -> &[[u8; 64]] {
+        &self.serialized
+    }
+
+    /// Private key of the `index`-th entry of [`public_keys`](Self::public_keys).
+    pub fn secret_key(&self, index: usize) -> SecretKey {
+        let offset = self.first + (index % BATCH_SIZE) as u64;
+        let tweak = Scalar::from_be_bytes(offset_bytes(offset)).unwrap();
+        let key = self
+            .start
+            .add_tweak(&tweak)
+            .expect("walk wrapped around to the zero key");
+
+        let lambda = Scalar::from_be_bytes(LAMBDA).unwrap();
+        match index / BATCH_SIZE {
+            0 => key,
+            1 => key.negate(),
+            2 => key.mul_tweak(&lambda).unwrap(),
+            3 => key.mul_tweak(&lambda).unwrap().negate(),
+            4 => key.mul_tweak(&lambda).unwrap().mul_tweak(&lambda).unwrap(),
+            _ => key
+                .mul_tweak(&lambda)
+                .unwrap()
+                .mul_tweak(&lambda)
+                .unwrap()
+                .negate(),
+        }
+    }
+}